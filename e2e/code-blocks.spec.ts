@@ -0,0 +1,38 @@
+import { expect, test } from "@playwright/test";
+
+test.beforeEach(async ({ page }) => {
+    await page.goto("/example-site/code-blocks");
+});
+
+test("has description from excerpt", async ({ page }) => {
+    await expect(page.locator('meta[name="description"]')).toHaveAttribute("content", /^Code shown in a post can be read from a file/);
+});
+
+test("has whole file included", async ({ page }) => {
+    const codeBlock = page.locator("figure[data-code-title]");
+    await expect(codeBlock.locator("figcaption")).toHaveText("src/main.rs");
+    await expect(codeBlock.locator("code")).toContainText("use std::collections::HashMap;");
+    await expect(codeBlock.locator("code")).not.toContainText("ANCHOR");
+});
+
+test("has range of lines included", async ({ page }) => {
+    const lineNumbers = page.locator("pre:has([data-line-number])");
+    await expect(lineNumbers.locator("code")).toContainText("fn main()");
+    await expect(lineNumbers.locator("[data-line-number]")).toHaveCount(4);
+});
+
+test("has region included", async ({ page }) => {
+    const region = page.locator("pre:has([data-highlighted])");
+    await expect(region.locator("code")).toContainText("fn count_words");
+    await expect(region.locator("code")).not.toContainText("fn main()");
+    await expect(region.locator("[data-highlighted]")).toContainText("counts.entry(word)");
+});
+
+test("has rust code checked like doc tests", async ({ page }) => {
+    const codeBlock = page.locator("pre").filter({ hasText: "assert_eq!(double(21), 42);" });
+    await expect(codeBlock).not.toContainText("fn double");
+
+    const playgroundLinks = page.getByRole("link", { name: "Run in the Rust playground" });
+    await expect(playgroundLinks).toHaveCount(3);
+    await expect(playgroundLinks.first()).toHaveAttribute("href", /^https:\/\/play\.rust-lang\.org\//);
+});
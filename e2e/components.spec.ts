@@ -0,0 +1,29 @@
+import { expect, test } from "@playwright/test";
+
+test.beforeEach(async ({ page }) => {
+    await page.goto("/example-site/components");
+});
+
+test("has audio with download link", async ({ page }) => {
+    const audio = page.getByTestId("Audio");
+    await expect(audio.locator("audio")).toHaveAttribute("src", "media/beep.wav");
+    await expect(audio).toContainText("A short beep");
+});
+
+test("has details revealing markdown", async ({ page }) => {
+    const details = page.getByTestId("Details");
+    await expect(details.locator("strong")).toBeHidden();
+    await details.getByText("Click to reveal").click();
+    await expect(details.locator("strong")).toHaveText("markdown");
+});
+
+test("has figure with caption", async ({ page }) => {
+    await expect(page.getByRole("figure", { name: "The Rust logo, in a figure" }))
+        .toBeVisible();
+});
+
+test("has embed card", async ({ page }) => {
+    await expect(page.getByTestId("Embed")).toHaveAttribute("href", "https://www.rust-lang.org");
+    await expect(page.getByTestId("Embed")).toHaveAttribute("rel", "noopener noreferrer");
+    await expect(page.getByTestId("Embed").locator("[data-external-link] svg")).toBeVisible();
+});
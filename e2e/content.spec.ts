@@ -18,8 +18,75 @@ test("has header", async ({ page }) => {
     // TODO: posted date
 });
 
+test("has reading time and word count", async ({ page }) => {
+    await expect(page.getByTestId("reading-time")).toHaveText(/^\d+ min read, \d+ words$/);
+});
+
 test("has navigation links", async ({ page }) => {
     await expect(page.getByRole("link", { name: "Home" })).toBeVisible();
     await expect(page.getByRole("link", { name: "Previous" })).toBeVisible();
     await expect(page.getByRole("link", { name: "Next" })).toBeVisible();
 });
+
+test("has footnotes with back links", async ({ page }) => {
+    const footnotes = page.getByRole("region", { name: "Footnotes" });
+    await expect(footnotes).toBeVisible();
+
+    await page.locator("#fnref-note a").click();
+    await expect(page).toHaveURL(/#fn-note$/);
+    await expect(footnotes.getByRole("link", { name: "Back to reference 1" }))
+        .toHaveAttribute("href", "#fnref-note");
+});
+
+test("has admonitions", async ({ page }) => {
+    for (const label of ["Note", "Tip", "Important", "Warning", "Caution"]) {
+        await expect(page.getByRole("note", { name: label })).toBeVisible();
+    }
+});
+
+test("has equations rendered to MathML", async ({ page }) => {
+    await expect(page.locator("math[display=block]").first()).toBeVisible();
+    await expect(page.locator("math annotation").first()).toHaveText(
+        "e^{i\\pi} + 1 = 0",
+    );
+});
+
+test("has code block with title, line numbers and highlighted lines", async ({ page }) => {
+    const codeBlock = page.locator("figure[data-code-title]");
+    await expect(codeBlock.locator("figcaption")).toHaveText("src/main.rs");
+    await expect(codeBlock.locator("[data-line-number]")).toHaveText(["1", "2", "3", "4"]);
+    await expect(codeBlock.locator("[data-highlighted]")).toHaveCount(2);
+});
+
+test("has copy button on code blocks", async ({ page }) => {
+    await expect(page.getByRole("button", { name: "Copy" }).first()).toBeVisible();
+});
+
+test("has wikilinks to other content", async ({ page }) => {
+    await expect(page.getByRole("link", { name: "Create a grid of images" })).toHaveAttribute("href", "/example-site/image-grid");
+    await expect(page.getByRole("link", { name: "this one" })).toHaveAttribute("href", "/example-site/code-blocks");
+});
+
+test("has external links marked with an icon", async ({ page }) => {
+    const link = page.getByRole("link", { name: "link", exact: true });
+    await expect(link).toHaveAttribute("rel", "noopener noreferrer");
+    await expect(link.locator("[data-external-link] svg")).toBeVisible();
+    await expect(page.getByRole("link", { name: "this one" })).not.toHaveAttribute("rel");
+});
+
+test("has smart punctuation", async ({ page }) => {
+    await expect(page.getByText("\u201cStraight quotes\u201d and dashes \u2013 like these \u2014 are made typographic\u2026 when enabled.")).toBeVisible();
+});
+
+test("has diagram rendered to SVG", async ({ page }) => {
+    const diagram = page.getByRole("img", { name: "Diagram" });
+    await expect(diagram).toBeVisible();
+    await expect(diagram.locator("svg")).toBeVisible();
+    await expect(diagram).toContainText("markdown");
+});
+
+test("has custom component", async ({ page }) => {
+    const callout = page.getByTestId("Callout");
+    await expect(callout).toContainText("Hello from a custom component!");
+    await expect(callout.locator("strong")).toHaveText("markdown");
+});
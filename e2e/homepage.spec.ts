@@ -30,6 +30,34 @@ test.fixme("has web feed", async ({ page }) => {
         .toBeVisible();
 });
 
+test("advertises atom, rss and json feeds", async ({ page, request }) => {
+    await expect(page.locator('link[rel="alternate"][type="application/atom+xml"]'))
+        .toHaveAttribute("href", "/example-site/atom.xml");
+    await expect(page.locator('link[rel="alternate"][type="application/rss+xml"]'))
+        .toHaveAttribute("href", "/example-site/rss.xml");
+
+    const rss = await (await request.get("/example-site/rss.xml")).text();
+    expect(rss).toContain('<rss version="2.0"');
+    expect(rss).toContain('<guid isPermaLink="false">urn:uuid:');
+
+    await expect(page.locator('link[rel="alternate"][type="application/feed+json"]'))
+        .toHaveAttribute("href", "/example-site/feed.json");
+    const json = await (await request.get("/example-site/feed.json")).json();
+    expect(json.version).toBe("https://jsonfeed.org/version/1.1");
+    expect(json.items[0].id).toMatch(/^urn:uuid:/);
+    expect(json.items[0].url).toMatch(/^http/);
+});
+
 test("has footer with promotion", async ({ page }) => {
     await expect(page.getByText("Page generated with leptos_ssg")).toBeVisible();
 });
+
+test("has reading time of content", async ({ page }) => {
+    const list = page.getByTestId("content-list");
+    await expect(list.getByTestId("reading-time").first()).toHaveText(/^\d+ min read$/);
+});
+
+test("has excerpt of content", async ({ page }) => {
+    const list = page.getByTestId("content-list");
+    await expect(list.getByTestId("excerpt").first()).toContainText("Code shown in a post can be read from a file");
+});
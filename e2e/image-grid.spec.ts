@@ -7,3 +7,30 @@ test.beforeEach(async ({ page }) => {
 test("has image grid", async ({ page }) => {
     await expect(page.getByTestId('ImageGrid')).toBeVisible();
 });
+
+test("has image grid with columns, captions and alt text", async ({ page }) => {
+    const grid = page.getByTestId('ImageGrid');
+    await expect(grid).toHaveClass(/grid-cols-3/);
+    await expect(grid.getByRole("figure", { name: "NixOS" })).toBeVisible();
+    await expect(grid.getByRole("img", { name: "Logo of the Rust language" })).toBeVisible();
+    await expect(grid.getByRole("img", { name: "forgejo.svg" })).toBeVisible();
+    await expect(grid.getByRole("link")).toHaveCount(5);
+});
+
+test("has images with alt text and size", async ({ page }) => {
+    const image = page.getByRole("img", { name: "The Rust logo" });
+    await expect(image).toHaveAttribute("width", "106");
+    await expect(image).toHaveAttribute("height", "106");
+});
+
+test("has figure with caption", async ({ page }) => {
+    await expect(page.getByRole("figure", { name: "The Naviarent logo, with a caption" }))
+        .toBeVisible();
+});
+
+test("has blurhash placeholders on raster images", async ({ page }) => {
+    await expect(page.getByRole("img", { name: "The Naviarent logo" }))
+        .toHaveAttribute("data-blurhash", /^[0-9A-Za-z#$%*+,\-.:;=?@[\]^_{|}~]{28}$/);
+    await expect(page.getByRole("img", { name: "The Rust logo" }))
+        .not.toHaveAttribute("data-blurhash");
+});
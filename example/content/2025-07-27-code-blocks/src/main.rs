@@ -0,0 +1,17 @@
+use std::collections::HashMap;
+
+// ANCHOR: count
+/// Count occurrences of each word in a text
+fn count_words(text: &str) -> HashMap<&str, usize> {
+    let mut counts = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    counts
+}
+// ANCHOR_END: count
+
+fn main() {
+    let counts = count_words("the quick brown fox jumps over the lazy dog");
+    println!("`the` appears {} times", counts["the"]);
+}
@@ -1,5 +1,26 @@
 use std::time::SystemTime;
 
+/// Custom component, highlighting a message with a title
+fn callout(
+    attributes: &leptos_ssg::Attributes,
+    children: Option<&str>,
+    _content: &leptos_ssg::Content,
+    _config: &leptos_ssg::BuildConfig,
+) -> Result<leptos::prelude::AnyView, leptos_ssg::ComponentError> {
+    use leptos::prelude::*;
+
+    let title = attributes.required("title")?.to_string();
+    let children = children.unwrap_or_default().to_string();
+
+    Ok(view! {
+        <div data-testid="Callout" class="my-4 p-4 border-2 border-dashed border-sky-900 dark:border-yellow-600">
+            <p class="font-bold">{title}</p>
+            <div inner_html=children></div>
+        </div>
+    }
+    .into_any())
+}
+
 fn main() {
     let sys_time = SystemTime::now();
     let timestamp = sys_time
@@ -19,7 +40,7 @@ fn main() {
     #[cfg(not(debug_assertions))]
     let base_url = "/leptos_ssg/";
 
-    let assets = "./assets/".into();
+    let assets = "./assets/";
     let target = leptos_ssg::Paths {
         target: "./target/example-site".into(),
         #[cfg(feature = "opengraph")]
@@ -45,7 +66,37 @@ fn main() {
         #[cfg(feature = "opengraph")]
         "http://localhost:4444",
     )
-    .unwrap();
+    .unwrap()
+    .with_responsive_images(leptos_ssg::ResponsiveImages {
+        widths: &[160, 320, 640, 1280],
+        #[cfg(debug_assertions)]
+        formats: &[leptos_ssg::ImageFormat::Webp],
+        #[cfg(not(debug_assertions))]
+        formats: &[leptos_ssg::ImageFormat::Avif, leptos_ssg::ImageFormat::Webp],
+        sizes: "(min-width: 768px) 75vw, 100vw",
+    })
+    .with_blurhash(leptos_ssg::Blurhash {
+        decoder_script: true,
+    })
+    .with_rust_check(leptos_ssg::RustCheck {
+        playground_link: true,
+    })
+    .with_typography(leptos_ssg::Typography {
+        smart_punctuation: true,
+        non_breaking_spaces: None,
+    })
+    .with_math()
+    .with_raw_html(leptos_ssg::RawHtml::Sanitize)
+    .with_external_links(leptos_ssg::ExternalLinks {
+        nofollow: false,
+        icon: true,
+        target: None,
+    })
+    .with_components(&[leptos_ssg::Component {
+        tag: "Callout",
+        render: callout,
+        feed: None,
+    }]);
     let content_path: std::path::PathBuf = "./content/".into();
     let mut blog = leptos_ssg::Blog::new(target, config);
 
@@ -184,7 +235,9 @@ if (
         .expect("processed markdown files");
 
     blog.add_content_assets(&content_path, &content);
-    blog.add_atom_feed(&content);
+    blog.add_atom_feed(&content).expect("atom feed");
+    blog.add_rss_feed(&content).expect("rss feed");
+    blog.add_json_feed(&content).expect("json feed");
 
     let path = blog.build().expect("files written to disk");
     println!("Wrote files to {}", path.display());
@@ -0,0 +1,224 @@
+mod audio;
+mod details;
+mod embed;
+mod figure;
+mod image_grid;
+mod video;
+
+use crate::config::BuildConfig;
+use crate::content::Content;
+use leptos::prelude::AnyView;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Files in content assets read by components, which are not published with the other assets
+pub(crate) const METADATA_FILES: &[&str] = &[image_grid::CAPTIONS_FILE];
+
+/// Create the view of a component, from its attributes and its children rendered in HTML
+pub type Render =
+    fn(&Attributes, Option<&str>, &Content, &BuildConfig) -> Result<AnyView, ComponentError>;
+
+/// Custom component, rendered in place of its HTML tag when written in markdown
+#[derive(Debug, Clone, Copy)]
+pub struct Component {
+    /// Name of the tag, case sensitive
+    pub tag: &'static str,
+    pub render: Render,
+    /// View in web feeds, where styles and scripts are not available.
+    /// Without it, only the children of the component are kept in feeds.
+    pub feed: Option<Render>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ComponentError {
+    #[error("missing required attribute `{0}`")]
+    MissingAttribute(String),
+    #[error("invalid value `{1}` for attribute `{0}`: {2}")]
+    InvalidAttribute(String, String, String),
+    #[error("asset `{0}` is not in the assets of the content")]
+    MissingAsset(String),
+    #[error("invalid asset `{0}`: {1}")]
+    InvalidAsset(String, String),
+}
+
+/// Components always available, after components provided in the build configuration
+pub(crate) const BUILT_IN: &[Component] = &[
+    Component {
+        tag: "ImageGrid",
+        render: image_grid::render,
+        feed: Some(image_grid::feed),
+    },
+    Component {
+        tag: "Video",
+        render: video::render,
+        feed: Some(video::feed),
+    },
+    Component {
+        tag: "Audio",
+        render: audio::render,
+        feed: Some(audio::feed),
+    },
+    Component {
+        tag: "Details",
+        render: details::render,
+        feed: Some(details::feed),
+    },
+    Component {
+        tag: "Figure",
+        render: figure::render,
+        feed: Some(figure::feed),
+    },
+    Component {
+        tag: "Embed",
+        render: embed::render,
+        feed: Some(embed::feed),
+    },
+];
+
+/// Find a component in an HTML block, with its attributes.
+/// Components provided in the configuration take precedence over built-in ones.
+pub(crate) fn find(config: &BuildConfig, html: &str) -> Option<(Component, Attributes)> {
+    let dom = match tl::parse(html, tl::ParserOptions::default()) {
+        Ok(dom) => dom,
+        Err(e) => {
+            println!("Failed to parse HTML: `{html}`: {e}");
+            return None;
+        }
+    };
+
+    config
+        .components
+        .iter()
+        .chain(BUILT_IN)
+        .find_map(|component| {
+            dom.nodes()
+                .iter()
+                .filter_map(|node| node.as_tag())
+                .find(|tag| tag.name() == component.tag)
+                .map(|tag| (*component, Attributes::from_tag(tag)))
+        })
+}
+
+/// How a component is written in an HTML block
+pub(crate) enum ComponentTag<'html> {
+    /// `<Component />`
+    SelfClosing,
+    /// `<Component>children</Component>`, with the markdown source of its children
+    Closed(&'html str),
+    /// `<Component>`, with the markdown source following it in the same block. The rest of the
+    /// children and the closing tag are in following blocks
+    Open(&'html str),
+}
+
+impl<'html> ComponentTag<'html> {
+    pub fn find(html: &'html str, tag: &str) -> Self {
+        let opening = format!("<{tag}");
+        let Some(start) = html
+            .match_indices(&opening)
+            .map(|(idx, _)| idx)
+            .find(|idx| {
+                html[idx + opening.len()..]
+                    .starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            })
+        else {
+            return Self::Open("");
+        };
+
+        // End of the opening tag, ignoring `>` in quoted attribute values
+        let mut quote = None;
+        let mut end = None;
+        for (idx, c) in html[start..].char_indices() {
+            match (c, quote) {
+                ('"' | '\'', None) => quote = Some(c),
+                (c, Some(q)) if c == q => quote = None,
+                ('>', None) => {
+                    end = Some(start + idx);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let Some(end) = end else {
+            return Self::Open("");
+        };
+
+        if html[..end].trim_end().ends_with('/') {
+            return Self::SelfClosing;
+        }
+        match html[end + 1..].rfind(&format!("</{tag}>")) {
+            Some(close) => Self::Closed(&html[end + 1..end + 1 + close]),
+            None => Self::Open(&html[end + 1..]),
+        }
+    }
+}
+
+/// URL of an asset of the content, for web feeds where relative URLs are not reliable
+fn absolute_asset_url(config: &BuildConfig, content: &Content, asset: &str) -> String {
+    format!("{}{}/{asset}", config.absolute_url(), content.slug())
+}
+
+/// Attributes of a component, as written in markdown
+#[derive(Debug, Clone, Default)]
+pub struct Attributes(BTreeMap<String, Option<String>>);
+
+impl Attributes {
+    pub(crate) fn from_tag(tag: &tl::HTMLTag) -> Self {
+        Self(
+            tag.attributes()
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.map(|value| value.to_string())))
+                .collect(),
+        )
+    }
+
+    /// Value of an attribute, `None` when it is missing or has no value
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).and_then(|value| value.as_deref())
+    }
+
+    /// Value of an attribute that must be present
+    pub fn required(&self, name: &str) -> Result<&str, ComponentError> {
+        self.get(name)
+            .ok_or_else(|| ComponentError::MissingAttribute(name.to_string()))
+    }
+
+    /// Path of an asset of the content, from an attribute that must be present.
+    /// The asset must exist in the assets of the content.
+    pub fn asset(&self, name: &str, content: &Content) -> Result<&str, ComponentError> {
+        let asset = self.required(name)?;
+        match content.assets_directory() {
+            Some(assets) if assets.join(asset).is_file() => Ok(asset),
+            _ => Err(ComponentError::MissingAsset(asset.to_string())),
+        }
+    }
+
+    /// Value of an attribute parsed to a type, `None` when it is missing or has no value
+    pub fn parse<T>(&self, name: &str) -> Result<Option<T>, ComponentError>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.get(name)
+            .map(|value| {
+                value.parse().map_err(|e: T::Err| {
+                    ComponentError::InvalidAttribute(
+                        name.to_string(),
+                        value.to_string(),
+                        e.to_string(),
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    /// Whether an attribute is present, with or without a value, like `open` in `<Details open>`
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_deref()))
+    }
+}
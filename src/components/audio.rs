@@ -0,0 +1,45 @@
+use super::{Attributes, ComponentError};
+use crate::config::BuildConfig;
+use crate::content::Content;
+use crate::html::prelude::*;
+
+/// Audio from the content assets, with a link to download it when audio is not supported
+pub(super) fn render(
+    attributes: &Attributes,
+    _children: Option<&str>,
+    content: &Content,
+    _config: &BuildConfig,
+) -> Result<AnyView, ComponentError> {
+    let src = attributes.asset("src", content)?.to_string();
+    let caption = attributes.get("caption").map(|caption| caption.to_string());
+
+    Ok(view! {
+        <figure data-testid="Audio" class=tw_join!("my-4")>
+            <audio controls preload="metadata" src=src.clone() loop=attributes.contains("loop") class=tw_join!("w-full")>
+                <a href=src.clone() class=tw_join!("underline", "text-sky-900", "dark:text-yellow-400")>
+                    "Download the audio"
+                </a>
+            </audio>
+            {caption.map(|caption| view! {
+                <figcaption class=tw_join!("mt-2", "text-center", "text-gray-600", "dark:text-gray-400")>{caption}</figcaption>
+            })}
+        </figure>
+    }
+    .into_any())
+}
+
+/// Link to the audio
+pub(super) fn feed(
+    attributes: &Attributes,
+    _children: Option<&str>,
+    content: &Content,
+    config: &BuildConfig,
+) -> Result<AnyView, ComponentError> {
+    let src = super::absolute_asset_url(config, content, attributes.required("src")?);
+    let caption = attributes.get("caption").unwrap_or("Audio").to_string();
+
+    Ok(view! {
+        <p><a href=src>{format!("♫ {caption}")}</a></p>
+    }
+    .into_any())
+}
@@ -0,0 +1,44 @@
+use super::{Attributes, ComponentError};
+use crate::config::BuildConfig;
+use crate::content::Content;
+use crate::html::prelude::*;
+
+/// Disclosure widget, its children are shown when opened
+pub(super) fn render(
+    attributes: &Attributes,
+    children: Option<&str>,
+    _content: &Content,
+    _config: &BuildConfig,
+) -> Result<AnyView, ComponentError> {
+    let summary = attributes.required("summary")?.to_string();
+    let children = children.unwrap_or_default().to_string();
+
+    Ok(view! {
+        <details
+            data-testid="Details"
+            open=attributes.contains("open")
+            class=tw_join!("my-4", "p-4", "border-2", "border-dashed", "border-gray-500")
+        >
+            <summary class=tw_join!("cursor-pointer", "text-lg", "font-bold")>{summary}</summary>
+            <div inner_html=children></div>
+        </details>
+    }
+    .into_any())
+}
+
+/// Summary as a heading, children always visible
+pub(super) fn feed(
+    attributes: &Attributes,
+    children: Option<&str>,
+    _content: &Content,
+    _config: &BuildConfig,
+) -> Result<AnyView, ComponentError> {
+    let summary = attributes.required("summary")?.to_string();
+    let children = children.unwrap_or_default().to_string();
+
+    Ok(view! {
+        <p><strong>{summary}</strong></p>
+        <div inner_html=children></div>
+    }
+    .into_any())
+}
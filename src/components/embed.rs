@@ -0,0 +1,75 @@
+use super::{Attributes, ComponentError};
+use crate::config::BuildConfig;
+use crate::content::Content;
+use crate::html::prelude::*;
+
+/// Card linking to an external page, with a thumbnail from the content assets.
+/// Nothing is loaded from the external website.
+pub(super) fn render(
+    attributes: &Attributes,
+    _children: Option<&str>,
+    content: &Content,
+    config: &BuildConfig,
+) -> Result<AnyView, ComponentError> {
+    let url = attributes.required("url")?.to_string();
+    let policy = config.external_link_policy(&url);
+    let title = attributes.required("title")?.to_string();
+    let image = match attributes.get("image") {
+        Some(_) => Some(attributes.asset("image", content)?.to_string()),
+        None => None,
+    };
+    let description = attributes
+        .get("description")
+        .map(|description| description.to_string());
+    let host = url
+        .split("://")
+        .nth(1)
+        .and_then(|url| url.split('/').next())
+        .unwrap_or_default()
+        .to_string();
+
+    Ok(view! {
+        <a
+            data-testid="Embed"
+            href=url
+            rel=policy.map(|policy| policy.rel())
+            target=policy.and_then(|policy| policy.target)
+            class=tw_join!("my-4", "flex", "gap-4", "items-center", "p-4", "border-2", "border-dashed", "border-sky-900", "dark:border-yellow-600")
+        >
+            {image.map(|image| view! {
+                <img loading="lazy" src=image alt="" class=tw_join!("h-auto", "max-w-32") />
+            })}
+            <span class=tw_join!("flex", "flex-col")>
+                <strong class=tw_join!("text-lg", "font-bold")>{title}</strong>
+                {description.map(|description| view! { <span>{description}</span> })}
+                <span class=tw_join!("text-gray-600", "dark:text-gray-400")>
+                    {host}
+                    {policy.filter(|policy| policy.icon).map(|_| external_link_icon())}
+                </span>
+            </span>
+        </a>
+    }
+    .into_any())
+}
+
+/// Link with its description
+pub(super) fn feed(
+    attributes: &Attributes,
+    _children: Option<&str>,
+    _content: &Content,
+    _config: &BuildConfig,
+) -> Result<AnyView, ComponentError> {
+    let url = attributes.required("url")?.to_string();
+    let title = attributes.required("title")?.to_string();
+    let description = attributes
+        .get("description")
+        .map(|description| description.to_string());
+
+    Ok(view! {
+        <p>
+            <a href=url>{title}</a>
+            {description.map(|description| view! { <br />{description} })}
+        </p>
+    }
+    .into_any())
+}
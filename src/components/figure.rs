@@ -0,0 +1,40 @@
+use super::{Attributes, ComponentError};
+use crate::config::BuildConfig;
+use crate::content::Content;
+use crate::html::prelude::*;
+
+/// Images written as children next to each other, with a caption
+pub(super) fn render(
+    attributes: &Attributes,
+    children: Option<&str>,
+    _content: &Content,
+    _config: &BuildConfig,
+) -> Result<AnyView, ComponentError> {
+    let caption = attributes.required("caption")?.to_string();
+    let children = children.unwrap_or_default().to_string();
+
+    Ok(view! {
+        <figure data-testid="Figure" class=tw_join!("my-4")>
+            <div class=tw_join!("flex", "flex-wrap", "justify-center", "items-center", "gap-4") inner_html=children></div>
+            <figcaption class=tw_join!("mt-2", "text-center", "text-gray-600", "dark:text-gray-400")>{caption}</figcaption>
+        </figure>
+    }
+    .into_any())
+}
+
+/// Images followed by the caption
+pub(super) fn feed(
+    attributes: &Attributes,
+    children: Option<&str>,
+    _content: &Content,
+    _config: &BuildConfig,
+) -> Result<AnyView, ComponentError> {
+    let caption = attributes.required("caption")?.to_string();
+    let children = children.unwrap_or_default().to_string();
+
+    Ok(view! {
+        <div inner_html=children></div>
+        <p><em>{caption}</em></p>
+    }
+    .into_any())
+}
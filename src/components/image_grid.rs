@@ -0,0 +1,289 @@
+use super::{Attributes, ComponentError};
+use crate::config::BuildConfig;
+use crate::content::{Content, escape_html};
+use crate::html::prelude::*;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Extensions of files included when the `extensions` attribute is missing
+const IMAGE_EXTENSIONS: &[&str] = &["avif", "gif", "jpeg", "jpg", "png", "svg", "webp"];
+
+/// Sidecar file in the folder of the images, with their captions and alt text:
+///
+/// ```toml
+/// ["rust.svg"]
+/// alt = "The Rust logo"
+/// caption = "Rust"
+/// ```
+pub(crate) const CAPTIONS_FILE: &str = "captions.toml";
+
+/// Order of the images in the grid
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+enum Sort {
+    #[default]
+    Name,
+    /// Date the file was last modified
+    Date,
+    /// Date the photo was taken, images without it come last
+    Exif,
+}
+
+impl FromStr for Sort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "name" => Ok(Self::Name),
+            "date" => Ok(Self::Date),
+            "exif" => Ok(Self::Exif),
+            _ => Err("expected `name`, `date` or `exif`".into()),
+        }
+    }
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Caption {
+    alt: Option<String>,
+    caption: Option<String>,
+}
+
+/// Image of the grid, with its path relative to the assets
+#[derive(Debug)]
+struct Image {
+    path: PathBuf,
+    alt: String,
+    caption: Option<String>,
+}
+
+/// Number of columns of the grid, classes are written in full for tailwind
+fn columns_class(attributes: &Attributes) -> Result<&'static str, ComponentError> {
+    match attributes.parse::<usize>("columns")? {
+        None | Some(2) => Ok("grid-cols-2"),
+        Some(1) => Ok("grid-cols-1"),
+        Some(3) => Ok("grid-cols-3"),
+        Some(4) => Ok("grid-cols-4"),
+        Some(5) => Ok("grid-cols-5"),
+        Some(6) => Ok("grid-cols-6"),
+        Some(columns) => Err(ComponentError::InvalidAttribute(
+            "columns".into(),
+            columns.to_string(),
+            "expected a number from 1 to 6".into(),
+        )),
+    }
+}
+
+/// Captions of the images in a folder, by path relative to the folder
+fn read_captions(directory: &Path, src: &str) -> Result<BTreeMap<String, Caption>, ComponentError> {
+    let file = directory.join(CAPTIONS_FILE);
+    let invalid = |e: String| {
+        ComponentError::InvalidAsset(Path::new(src).join(CAPTIONS_FILE).display().to_string(), e)
+    };
+
+    match std::fs::read_to_string(&file) {
+        Ok(captions) => toml::from_str(&captions).map_err(|e| invalid(e.to_string())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(invalid(e.to_string())),
+    }
+}
+
+/// Date a photo was taken, from its EXIF metadata
+fn exif_date_time(path: &Path) -> Option<String> {
+    let file = std::fs::File::open(path).ok()?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut std::io::BufReader::new(file))
+        .ok()?;
+    let field = exif
+        .get_field(exif::Tag::DateTimeOriginal, exif::In::PRIMARY)
+        .or_else(|| exif.get_field(exif::Tag::DateTime, exif::In::PRIMARY))?;
+
+    // Formatted as `YYYY-MM-DD HH:MM:SS`, sorted like dates
+    Some(field.display_value().to_string())
+}
+
+/// Images located in a folder of the content assets, relative to the assets, filtered by
+/// extension and sorted with the attributes of the grid
+fn list_images(
+    assets: &Path,
+    src: &str,
+    attributes: &Attributes,
+) -> Result<Vec<Image>, ComponentError> {
+    let directory = assets.join(src);
+    let extensions: Vec<String> = match attributes.get("extensions") {
+        Some(extensions) => extensions
+            .split(',')
+            .map(|extension| extension.trim().trim_start_matches('.').to_lowercase())
+            .filter(|extension| !extension.is_empty())
+            .collect(),
+        None => IMAGE_EXTENSIONS.iter().map(|e| e.to_string()).collect(),
+    };
+    let sort = attributes.parse::<Sort>("sort")?.unwrap_or_default();
+
+    // Collect list of images
+    let mut list_images = walkdir::WalkDir::new(&directory)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|dir_entry| dir_entry.into_path())
+        .filter(|path| path.is_file() && !path.ends_with(CAPTIONS_FILE))
+        .filter(|path| {
+            path.extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| extensions.contains(&extension.to_lowercase()))
+        })
+        .collect::<Vec<_>>();
+
+    // Sort by name, then by date if requested
+    list_images.sort();
+    match sort {
+        Sort::Name => {}
+        Sort::Date => list_images.sort_by_cached_key(|path| {
+            std::fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        }),
+        Sort::Exif => list_images.sort_by_cached_key(|path| {
+            let date_time = exif_date_time(path);
+            (date_time.is_none(), date_time)
+        }),
+    }
+    if attributes.contains("reverse") {
+        list_images.reverse();
+    }
+
+    let mut captions = read_captions(&directory, src)?;
+    let list_images = list_images
+        .into_iter()
+        .filter_map(|path| {
+            let name = path
+                .strip_prefix(&directory)
+                .ok()?
+                .to_str()?
+                .replace('\\', "/");
+            let filename = path.file_name()?.to_str()?.to_string();
+            let caption = captions.remove(&name).unwrap_or_default();
+            Some(Image {
+                // Get relative path to be accepted in the html
+                path: path.strip_prefix(assets).ok()?.to_path_buf(),
+                alt: caption.alt.unwrap_or(filename),
+                caption: caption.caption,
+            })
+        })
+        .collect();
+
+    for name in captions.keys() {
+        println!(
+            "ImageGrid: `{CAPTIONS_FILE}` has a caption for `{name}`, which is not in the grid"
+        );
+    }
+
+    Ok(list_images)
+}
+
+/// Grid of the images located in a folder of the content assets
+pub(super) fn render(
+    attributes: &Attributes,
+    _children: Option<&str>,
+    content: &Content,
+    config: &BuildConfig,
+) -> Result<AnyView, ComponentError> {
+    let src = attributes.required("src")?;
+    let Some(assets) = content.assets_directory() else {
+        // If there is no assets, then there is no html to render
+        return Ok(().into_any());
+    };
+
+    let columns = columns_class(attributes)?;
+    let list_images = list_images(assets, src, attributes)?;
+
+    println!("ImageGrid: Files retained: {list_images:#?}");
+
+    // For each image, create html view
+    let list_images = list_images
+        .into_iter().map(|Image { path, alt, caption }| {
+
+        // Blurred placeholder while the image is loading
+        let placeholder = config.blurhash.and_then(|_| crate::image_placeholder::placeholder(&assets.join(&path)));
+
+        // Resized variants of the image, thumbnails are small
+        let picture = config.responsive_images.and_then(|responsive_images| {
+            let variants = crate::responsive_images::variants(&assets.join(&path), &responsive_images);
+            (!variants.is_empty()).then(|| {
+                let original_width = Content::image_size(&assets.join(&path)).map(|(width, _)| width);
+                crate::responsive_images::picture(
+                    &escape_html(path.to_str().unwrap()),
+                    &format!(
+                        " loading=\"lazy\" alt=\"{}\"{} class=\"{}\"",
+                        escape_html(&alt),
+                        placeholder.as_ref().map(|placeholder| placeholder.attributes()).unwrap_or_default(),
+                        tw_join!("h-auto", "max-w-32"),
+                    ),
+                    &variants,
+                    original_width,
+                    "8rem",
+                    &responsive_images,
+                )
+            })
+        });
+        let path = path.to_str().unwrap();
+        let (blurhash, style) = placeholder.map(|placeholder| placeholder.values()).unzip();
+
+        let link = match picture {
+            Some(picture) => view! {
+                <a class=tw_join!("w-full", "h-full", "border-2", "border-dashed", "border-sky-900", "dark:border-yellow-600") href={path.to_string()} inner_html=picture></a>
+            }.into_any(),
+            None => view! {
+                <a class=tw_join!("w-full", "h-full", "border-2", "border-dashed", "border-sky-900", "dark:border-yellow-600") href={path.to_string()}>
+                    <img loading="lazy" class=tw_join!("h-auto", "max-w-32") src={path.to_string()} alt=alt data-blurhash=blurhash style=style.flatten() />
+                </a>
+            }.into_any(),
+        };
+
+        match caption {
+            Some(caption) => view! {
+                <figure class=tw_join!("flex", "flex-col")>
+                    {link}
+                    <figcaption class=tw_join!("mt-2", "text-center", "text-sm", "text-gray-600", "dark:text-gray-400")>{caption}</figcaption>
+                </figure>
+            }.into_any(),
+            None => link,
+        }
+    }).collect_view();
+
+    // Final view with images
+    Ok(view! {
+        <div data-testid="ImageGrid" class=tw_join!("my-4", "grid", columns, "gap-5")>
+            {list_images}
+        </div>
+    }
+    .into_any())
+}
+
+/// Images one after the other, with absolute URLs
+pub(super) fn feed(
+    attributes: &Attributes,
+    _children: Option<&str>,
+    content: &Content,
+    config: &BuildConfig,
+) -> Result<AnyView, ComponentError> {
+    let src = attributes.required("src")?;
+    let Some(assets) = content.assets_directory() else {
+        return Ok(().into_any());
+    };
+
+    let list_images = list_images(assets, src, attributes)?
+        .into_iter()
+        .filter_map(|Image { path, alt, caption }| {
+            let url = super::absolute_asset_url(config, content, path.to_str()?);
+            let href = url.clone();
+            Some(view! {
+                <p>
+                    <a href=href><img src=url alt=alt /></a>
+                    {caption.map(|caption| view! { <br />{caption} })}
+                </p>
+            })
+        })
+        .collect_view();
+
+    Ok(list_images.into_any())
+}
@@ -0,0 +1,63 @@
+use super::{Attributes, ComponentError};
+use crate::config::BuildConfig;
+use crate::content::Content;
+use crate::html::prelude::*;
+
+/// Video from the content assets, with a link to download it when videos are not supported
+pub(super) fn render(
+    attributes: &Attributes,
+    _children: Option<&str>,
+    content: &Content,
+    _config: &BuildConfig,
+) -> Result<AnyView, ComponentError> {
+    let src = attributes.asset("src", content)?.to_string();
+    let poster = match attributes.get("poster") {
+        Some(_) => Some(attributes.asset("poster", content)?.to_string()),
+        None => None,
+    };
+    let caption = attributes.get("caption").map(|caption| caption.to_string());
+
+    Ok(view! {
+        <figure data-testid="Video" class=tw_join!("my-4")>
+            <video
+                controls
+                preload="metadata"
+                src=src.clone()
+                poster=poster
+                loop=attributes.contains("loop")
+                muted=attributes.contains("muted")
+                class=tw_join!("w-full", "h-auto")
+            >
+                <a href=src.clone() class=tw_join!("underline", "text-sky-900", "dark:text-yellow-400")>
+                    "Download the video"
+                </a>
+            </video>
+            {caption.map(|caption| view! {
+                <figcaption class=tw_join!("mt-2", "text-center", "text-gray-600", "dark:text-gray-400")>{caption}</figcaption>
+            })}
+        </figure>
+    }
+    .into_any())
+}
+
+/// Poster or caption linking to the video
+pub(super) fn feed(
+    attributes: &Attributes,
+    _children: Option<&str>,
+    content: &Content,
+    config: &BuildConfig,
+) -> Result<AnyView, ComponentError> {
+    let src = super::absolute_asset_url(config, content, attributes.required("src")?);
+    let caption = attributes.get("caption").unwrap_or("Video").to_string();
+    let poster = attributes
+        .get("poster")
+        .map(|poster| super::absolute_asset_url(config, content, poster));
+
+    Ok(view! {
+        <p>
+            {poster.map(|poster| view! { <a href=src.clone()><img src=poster alt=caption.clone() /></a><br /> })}
+            <a href=src.clone()>{format!("▶ {caption}")}</a>
+        </p>
+    }
+    .into_any())
+}
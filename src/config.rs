@@ -1,3 +1,7 @@
+use crate::components::Component;
+use crate::content::{RustCheck, Typography};
+use crate::image_placeholder::Blurhash;
+use crate::responsive_images::ResponsiveImages;
 use jiff::Timestamp;
 use std::str::FromStr;
 use uuid::Uuid;
@@ -18,6 +22,14 @@ pub struct BuildConfig<'a> {
     pub(crate) feed_uuid: Uuid,
     #[cfg(feature = "opengraph")]
     pub(crate) webdriver: &'a str,
+    pub(crate) responsive_images: Option<ResponsiveImages<'a>>,
+    pub(crate) blurhash: Option<Blurhash>,
+    pub(crate) rust_check: Option<RustCheck>,
+    pub(crate) raw_html: RawHtml,
+    pub(crate) math: bool,
+    pub(crate) typography: Typography,
+    pub(crate) external_links: ExternalLinks<'a>,
+    pub(crate) components: &'a [Component],
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -27,6 +39,41 @@ pub struct Styles<'a> {
     pub opengraph: &'a str,
 }
 
+/// Attributes of links pointing outside of `host`, which always get `rel="noopener noreferrer"`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExternalLinks<'a> {
+    /// Add `nofollow` to `rel`
+    pub nofollow: bool,
+    /// Show an icon after the text of the link
+    pub icon: bool,
+    /// Where to open the link, such as `_blank`
+    pub target: Option<&'a str>,
+}
+
+impl ExternalLinks<'_> {
+    /// Value of `rel` on external links
+    pub(crate) fn rel(&self) -> &'static str {
+        match self.nofollow {
+            true => "noopener noreferrer nofollow",
+            false => "noopener noreferrer",
+        }
+    }
+}
+
+/// What to do with raw HTML written in markdown, other than custom components
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum RawHtml {
+    /// Leave HTML out of the output
+    #[default]
+    Drop,
+    /// Output HTML as written
+    Passthrough,
+    /// Only keep an allow-list of tags and attributes
+    Sanitize,
+    /// Fail the build
+    Reject,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum BuildConfigError<'a> {
     #[error("A trailing slash `/` is required at the end for `{0}`")]
@@ -40,6 +87,7 @@ pub enum BuildConfigError<'a> {
 }
 
 impl<'a> BuildConfig<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         host: &'a str,
         base_url: &'a str,
@@ -83,10 +131,88 @@ impl<'a> BuildConfig<'a> {
             feed_uuid,
             #[cfg(feature = "opengraph")]
             webdriver,
+            responsive_images: None,
+            blurhash: None,
+            rust_check: None,
+            raw_html: RawHtml::default(),
+            math: false,
+            typography: Typography::default(),
+            external_links: ExternalLinks::default(),
+            components: &[],
         })
     }
 
+    /// Generate resized variants of raster images located in content assets
+    pub fn with_responsive_images(mut self, responsive_images: ResponsiveImages<'a>) -> Self {
+        self.responsive_images = Some(responsive_images);
+        self
+    }
+
+    /// Compute blurred placeholders of raster images located in content assets
+    pub fn with_blurhash(mut self, blurhash: Blurhash) -> Self {
+        self.blurhash = Some(blurhash);
+        self
+    }
+
+    /// Compile-check `rust` code blocks with the local toolchain
+    pub fn with_rust_check(mut self, rust_check: RustCheck) -> Self {
+        self.rust_check = Some(rust_check);
+        self
+    }
+
+    /// Render LaTeX equations written between `$` or `$$` to MathML. Off by default, dollar
+    /// signs of prices would be read as equations.
+    pub fn with_math(mut self) -> Self {
+        self.math = true;
+        self
+    }
+
+    /// Apply typographic rules to content and titles
+    pub fn with_typography(mut self, typography: Typography) -> Self {
+        self.typography = typography;
+        self
+    }
+
+    /// Set what to do with raw HTML written in markdown, left out by default
+    pub fn with_raw_html(mut self, raw_html: RawHtml) -> Self {
+        self.raw_html = raw_html;
+        self
+    }
+
+    /// Set attributes of links pointing outside of the website
+    pub fn with_external_links(mut self, external_links: ExternalLinks<'a>) -> Self {
+        self.external_links = external_links;
+        self
+    }
+
+    /// Register custom components, rendered in place of their tag in markdown
+    pub fn with_components(mut self, components: &'a [Component]) -> Self {
+        self.components = components;
+        self
+    }
+
     pub fn absolute_url(&self) -> String {
         format!("{}{}", self.host, self.base_url)
     }
+
+    /// Whether a link points outside of `host`
+    pub(crate) fn is_external_link(&self, url: &str) -> bool {
+        let absolute = ["http://", "https://", "//"]
+            .iter()
+            .any(|scheme| url.starts_with(scheme));
+        let internal = url
+            .strip_prefix(self.host)
+            .or_else(|| {
+                // Links without scheme to the same host
+                let host = self.host.split_once("//").map(|(_, host)| host)?;
+                url.strip_prefix("//")?.strip_prefix(host)
+            })
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '?', '#']));
+        absolute && !internal
+    }
+
+    /// Policy applying to a link, when it points outside of `host`
+    pub(crate) fn external_link_policy(&self, url: &str) -> Option<ExternalLinks<'a>> {
+        self.is_external_link(url).then_some(self.external_links)
+    }
 }
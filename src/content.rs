@@ -1,13 +1,31 @@
+mod code_block;
 mod content_id;
+mod diagram;
+mod include;
+mod math;
 mod metadata;
-
+mod rust_check;
+mod sanitize;
+pub(crate) mod syntax_highlight;
+mod typography;
+
+use crate::components::{Attributes, Component, ComponentError, ComponentTag};
+use crate::config::{BuildConfig, RawHtml};
+use code_block::CodeBlockInfo;
+use diagram::Diagram;
 use metadata::*;
 use pulldown_cmark::Event;
 use pulldown_cmark::Tag;
 use pulldown_cmark::TagEnd;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
 use tailwind_fuse::tw_join;
 
+pub use include::IncludeError;
+pub use rust_check::RustCheck;
+pub use typography::{NonBreakingSpaces, Typography};
+
 #[derive(Debug, Clone)]
 pub struct Content {
     path: PathBuf,
@@ -19,10 +37,22 @@ pub struct Content {
     // Navigation
     previous: Option<Slug>,
     next: Option<Slug>,
+
+    /// Targets of wikilinks found in the content list, with their slug
+    wikilinks: Vec<(String, Slug)>,
+
+    /// Diagrams rendered to SVG with their source, shared by the page and the files of diagrams
+    rendered_diagrams: Arc<Mutex<HashMap<(Diagram, String), String>>>,
 }
 
 pub type Slug = String;
 
+/// Words read per minute, to estimate reading time
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Marker ending the excerpt of the content
+const EXCERPT_MARKER: &str = "<!-- more -->";
+
 #[derive(Debug, thiserror::Error)]
 pub enum ContentListError {
     #[error("Failed to read Content")]
@@ -77,6 +107,8 @@ impl Content {
                     assets,
                     previous: None,
                     next: None,
+                    wikilinks: vec![],
+                    rendered_diagrams: Default::default(),
                 });
             }
         }
@@ -97,9 +129,54 @@ impl Content {
             }
         }
 
+        Self::resolve_wikilinks(&mut vec);
+
         Ok(vec)
     }
 
+    /// Resolve wikilinks by title or slug, unresolved ones fail when generating html
+    fn resolve_wikilinks(list: &mut [Self]) {
+        let titles_and_slugs = list
+            .iter()
+            .map(|content| (content.meta().title().to_lowercase(), content.slug()))
+            .collect::<Vec<_>>();
+        for content in list.iter_mut() {
+            content.wikilinks = content
+                .wikilink_targets()
+                .into_iter()
+                .filter_map(|target| {
+                    titles_and_slugs
+                        .iter()
+                        .find(|(title, slug)| *title == target.to_lowercase() || *slug == target)
+                        .map(|(_, slug)| (target, slug.clone()))
+                })
+                .collect();
+        }
+    }
+
+    /// Targets of `[[wikilinks]]` in the content
+    fn wikilink_targets(&self) -> Vec<String> {
+        Self::markdown_events(&self.raw)
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Start(Tag::Link {
+                    link_type: pulldown_cmark::LinkType::WikiLink { .. },
+                    dest_url,
+                    ..
+                }) => Some(dest_url.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Slug of the content targeted by a wikilink
+    fn wikilink(&self, target: &str) -> Option<&str> {
+        self.wikilinks
+            .iter()
+            .find(|(wikilink, _)| wikilink == target)
+            .map(|(_, slug)| slug.as_str())
+    }
+
     /// Sort by descending order, the first item is the newest one
     fn sort_desc(vec: Vec<Self>) -> Vec<Self> {
         let mut vec = vec;
@@ -111,6 +188,11 @@ impl Content {
         self.slug.clone()
     }
 
+    /// Title from the metadata, with the typography of the build
+    pub fn title(&self, config: &BuildConfig) -> String {
+        config.typography.apply_to_plain_text(self.meta.title())
+    }
+
     pub fn meta(&self) -> &MetadataList {
         &self.meta
     }
@@ -119,6 +201,12 @@ impl Content {
         self.assets.as_deref()
     }
 
+    /// Folder on disk containing the assets of the content, when it has some
+    pub fn assets_directory(&self) -> Option<&Path> {
+        self.assets.as_ref()?;
+        self.path.parent()
+    }
+
     pub fn previous(&self) -> Option<&str> {
         self.previous.as_deref()
     }
@@ -128,67 +216,527 @@ impl Content {
     }
 
     fn markdown_events<'input>(input: &'input str) -> Vec<Event<'input>> {
+        let parser = pulldown_cmark::Parser::new_ext(input, Self::markdown_options());
+
+        let iterator = pulldown_cmark::TextMergeStream::new(parser);
+        iterator.collect()
+    }
+
+    /// Events of markdown, with the typography of the build
+    fn typographic_markdown_events<'input>(
+        input: &'input str,
+        config: &BuildConfig,
+    ) -> Vec<Event<'input>> {
+        let mut options = Self::markdown_options();
+        if config.math {
+            options.insert(pulldown_cmark::Options::ENABLE_MATH);
+        }
+        if config.typography.smart_punctuation {
+            options.insert(pulldown_cmark::Options::ENABLE_SMART_PUNCTUATION);
+        }
+        let parser = pulldown_cmark::Parser::new_ext(input, options);
+
+        let iterator = pulldown_cmark::TextMergeStream::new(parser);
+        config.typography.apply_to_events(iterator.collect())
+    }
+
+    fn markdown_options() -> pulldown_cmark::Options {
         let mut options = pulldown_cmark::Options::empty();
         options.insert(pulldown_cmark::Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS);
         options.insert(pulldown_cmark::Options::ENABLE_TABLES);
         options.insert(pulldown_cmark::Options::ENABLE_TASKLISTS);
-        let parser = pulldown_cmark::Parser::new_ext(input, options);
-
-        let iterator = pulldown_cmark::TextMergeStream::new(parser);
-        iterator.collect()
+        options.insert(pulldown_cmark::Options::ENABLE_FOOTNOTES);
+        options.insert(pulldown_cmark::Options::ENABLE_STRIKETHROUGH);
+        options.insert(pulldown_cmark::Options::ENABLE_SUPERSCRIPT);
+        options.insert(pulldown_cmark::Options::ENABLE_SUBSCRIPT);
+        options.insert(pulldown_cmark::Options::ENABLE_DEFINITION_LIST);
+        options.insert(pulldown_cmark::Options::ENABLE_GFM);
+        options.insert(pulldown_cmark::Options::ENABLE_WIKILINKS);
+        options
     }
 
-    pub fn raw_html(&self, aboslute_url: &str) -> String {
+    pub fn raw_html(&self, config: &BuildConfig) -> Result<String, GenerateHtmlError> {
+        let absolute_url = config.absolute_url();
+
         // Add instructions to read article on the website if formatting looks weird in feed reader
         let feed_bad_formatting_disclaimer = format!(
-            "\n\n[If the formatting of this post looks odd in your feed reader, [visit the original article]({aboslute_url}{}/)]\n",
+            "\n\n[If the formatting of this post looks odd in your feed reader, [visit the original article]({absolute_url}{}/)]\n",
             self.slug()
         );
         let mut content_with_disclaimer = self.raw.clone();
         content_with_disclaimer.push_str(feed_bad_formatting_disclaimer.as_ref());
 
+        // Link footnote references to the original article, feed readers do not reliably keep
+        // anchors inside entries
+        let mut footnote_labels: Vec<String> = vec![];
+        // Diagrams are replaced by their SVG file, scripts and inline SVG are often stripped
+        let mut diagram = false;
+        let mut diagram_number = 0;
+        // Code of blocks including a file replaces their own
+        let mut included = false;
+        let markdown_events =
+            Self::typographic_markdown_events(content_with_disclaimer.as_ref(), config)
+                .into_iter()
+                .flat_map(|event| match event {
+                    Event::Start(Tag::CodeBlock(pulldown_cmark::CodeBlockKind::Fenced(
+                        ref info,
+                    ))) if rendered_diagram(info).is_some() => {
+                        diagram = true;
+                        vec![]
+                    }
+                    // Titles of code blocks are written above them
+                    Event::Start(Tag::CodeBlock(pulldown_cmark::CodeBlockKind::Fenced(info))) => {
+                        let info = CodeBlockInfo::parse(&info);
+                        let mut events = vec![];
+                        if let Some(title) = &info.title {
+                            events.push(Event::Html(
+                                format!("<p><strong>{}</strong></p>", escape_html(title)).into(),
+                            ));
+                        }
+                        events.push(Event::Start(Tag::CodeBlock(
+                            pulldown_cmark::CodeBlockKind::Fenced(
+                                info.language.clone().unwrap_or_default().into(),
+                            ),
+                        )));
+                        if info.get("file").is_some() {
+                            match self.code_block_code(&info, String::new()) {
+                                Ok(code) => {
+                                    included = true;
+                                    events.push(Event::Text(code.into()));
+                                }
+                                Err(e) => println!("Feed: {e}"),
+                            }
+                        }
+                        events
+                    }
+                    Event::Text(_) if diagram || included => vec![],
+                    Event::End(TagEnd::CodeBlock) if included => {
+                        included = false;
+                        vec![Event::End(TagEnd::CodeBlock)]
+                    }
+                    Event::End(TagEnd::CodeBlock) if diagram => {
+                        diagram = false;
+                        diagram_number += 1;
+                        vec![Event::Html(
+                            format!(
+                                "<p><img src=\"{absolute_url}{}/{}\" alt=\"Diagram\"></p>",
+                                self.slug(),
+                                diagram::file_name(diagram_number)
+                            )
+                            .into(),
+                        )]
+                    }
+                    // Feed readers drop the classes of admonitions, write their label instead
+                    Event::Start(Tag::BlockQuote(Some(kind))) => vec![
+                        Event::Start(Tag::BlockQuote(Some(kind))),
+                        Event::Html(
+                            format!("<p><strong>{}</strong></p>", Self::admonition_label(kind))
+                                .into(),
+                        ),
+                    ],
+                    // Wikilinks point to other content on the website
+                    Event::Start(Tag::Link {
+                        link_type: link_type @ pulldown_cmark::LinkType::WikiLink { .. },
+                        dest_url,
+                        title,
+                        id,
+                    }) => {
+                        let dest_url = match self.wikilink(&dest_url) {
+                            Some(slug) => format!("{absolute_url}{slug}/").into(),
+                            None => dest_url,
+                        };
+                        vec![Event::Start(Tag::Link {
+                            link_type,
+                            dest_url,
+                            title,
+                            id,
+                        })]
+                    }
+                    // Feed readers rarely support MathML, show the source of equations instead
+                    Event::InlineMath(tex) => {
+                        vec![Event::Html(math::to_fallback(&tex, false).into())]
+                    }
+                    Event::DisplayMath(tex) => {
+                        vec![Event::Html(math::to_fallback(&tex, true).into())]
+                    }
+                    Event::FootnoteReference(label) => {
+                        let number = match footnote_labels.iter().position(|el| *el == *label) {
+                            Some(idx) => idx + 1,
+                            None => {
+                                footnote_labels.push(label.to_string());
+                                footnote_labels.len()
+                            }
+                        };
+                        vec![Event::Html(
+                            format!(
+                                "<sup><a href=\"{absolute_url}{}/#{}\">{number}</a></sup>",
+                                self.slug(),
+                                Self::footnote_definition_id(&label)
+                            )
+                            .into(),
+                        )]
+                    }
+                    event => vec![event],
+                });
+
+        // Components are replaced by their view for feeds. Components with children in following
+        // blocks keep their events until their closing tag. HTML written in the content goes
+        // through the raw HTML policy, HTML generated above is never inline nor in a block.
+        let mut open_components: Vec<(Component, Attributes, Vec<Event>)> = vec![];
+        let mut events = vec![];
+        let mut html_block: Option<String> = None;
+        // Events are added to the innermost open component
+        fn parent_events<'e, 'a>(
+            open_components: &'e mut [(Component, Attributes, Vec<Event<'a>>)],
+            events: &'e mut Vec<Event<'a>>,
+        ) -> &'e mut Vec<Event<'a>> {
+            match open_components.last_mut() {
+                Some((_, _, children)) => children,
+                None => events,
+            }
+        }
+        for event in markdown_events {
+            match (event, html_block.as_mut()) {
+                (Event::Start(Tag::HtmlBlock), _) => html_block = Some(String::new()),
+                (Event::Html(html), Some(block_html)) => block_html.push_str(&html),
+                (Event::End(TagEnd::HtmlBlock), Some(_)) => {
+                    let html = html_block.take().unwrap_or_default();
+
+                    if open_components.last().is_some_and(|(component, _, _)| {
+                        html.trim() == format!("</{}>", component.tag)
+                    }) {
+                        if let Some((component, attributes, children)) = open_components.pop() {
+                            let mut children_html = String::new();
+                            pulldown_cmark::html::push_html(
+                                &mut children_html,
+                                children.into_iter(),
+                            );
+                            let view = self.feed_component(
+                                &component,
+                                &attributes,
+                                &children_html,
+                                config,
+                            );
+                            parent_events(&mut open_components, &mut events)
+                                .push(Event::Html(view.into()));
+                        }
+                        continue;
+                    }
+
+                    match crate::components::find(config, &html) {
+                        Some((component, attributes)) => {
+                            match ComponentTag::find(&html, component.tag) {
+                                ComponentTag::SelfClosing => parent_events(
+                                    &mut open_components,
+                                    &mut events,
+                                )
+                                .push(Event::Html(
+                                    self.feed_component(&component, &attributes, "", config)
+                                        .into(),
+                                )),
+                                ComponentTag::Closed(children) => {
+                                    let children_html = self.feed_html(config, children)?;
+                                    parent_events(&mut open_components, &mut events).push(
+                                        Event::Html(
+                                            self.feed_component(
+                                                &component,
+                                                &attributes,
+                                                &children_html,
+                                                config,
+                                            )
+                                            .into(),
+                                        ),
+                                    );
+                                }
+                                ComponentTag::Open(children) => {
+                                    // Children can start in the block of the opening tag
+                                    let children_html = self.feed_html(config, children)?;
+                                    let children = match children_html.is_empty() {
+                                        true => vec![],
+                                        false => vec![Event::Html(children_html.into())],
+                                    };
+                                    open_components.push((component, attributes, children));
+                                }
+                            }
+                        }
+                        None => parent_events(&mut open_components, &mut events).push(Event::Html(
+                            self.apply_raw_html_policy(config, &html, false)?.into(),
+                        )),
+                    }
+                }
+                (Event::InlineHtml(html), _) => parent_events(&mut open_components, &mut events)
+                    .push(Event::InlineHtml(
+                        self.apply_raw_html_policy(config, &html, true)?.into(),
+                    )),
+                (event, _) => parent_events(&mut open_components, &mut events).push(event),
+            }
+        }
+        // Components never closed only keep their children
+        for (_, _, children) in open_components {
+            events.extend(children);
+        }
+
         // Parse markdown and write html
-        let markdown_events = Self::markdown_events(content_with_disclaimer.as_ref());
         let mut html_output = String::new();
-        pulldown_cmark::html::push_html(&mut html_output, markdown_events.into_iter());
+        pulldown_cmark::html::push_html(&mut html_output, events.into_iter());
 
-        html_output
+        Ok(html_output)
     }
 
-    fn syntax_highlight_mapping(language: impl AsRef<str>) -> String {
-        match language.as_ref() {
-            "html" => "xml",
-            language => language,
+    /// HTML of markdown children of components for web feeds, with the raw HTML policy applied
+    fn feed_html(&self, config: &BuildConfig, markdown: &str) -> Result<String, GenerateHtmlError> {
+        let mut events = vec![];
+        let mut html_block: Option<String> = None;
+        for event in Self::typographic_markdown_events(markdown, config) {
+            match (event, html_block.as_mut()) {
+                (Event::Start(Tag::HtmlBlock), _) => html_block = Some(String::new()),
+                (Event::Html(html), Some(block_html)) => block_html.push_str(&html),
+                (Event::End(TagEnd::HtmlBlock), Some(_)) => {
+                    let html = html_block.take().unwrap_or_default();
+                    events.push(Event::Html(
+                        self.apply_raw_html_policy(config, &html, false)?.into(),
+                    ));
+                }
+                (Event::InlineHtml(html), _) => events.push(Event::InlineHtml(
+                    self.apply_raw_html_policy(config, &html, true)?.into(),
+                )),
+                (event, _) => events.push(event),
+            }
+        }
+
+        let mut html_output = String::new();
+        pulldown_cmark::html::push_html(&mut html_output, events.into_iter());
+        Ok(html_output)
+    }
+
+    /// Render a component for web feeds, falling back on its children
+    fn feed_component(
+        &self,
+        component: &Component,
+        attributes: &Attributes,
+        children: &str,
+        config: &BuildConfig,
+    ) -> String {
+        let Some(feed) = component.feed else {
+            return children.to_string();
+        };
+        let children = (!children.is_empty()).then_some(children);
+        match feed(attributes, children, self, config) {
+            Ok(view) => leptos::prelude::RenderHtml::to_html(view),
+            Err(e) => {
+                println!(
+                    "Feed: failed to render component `{}` in `{}`: {e}",
+                    component.tag,
+                    self.path.display()
+                );
+                children.unwrap_or_default().to_string()
+            }
         }
-        .into()
     }
 
-    /// Collect languages found in code blocks in markdown content
+    /// Collect languages found in code blocks in markdown content, diagrams are not highlighted
     pub fn code_block_languages(&self) -> impl Iterator<Item = impl AsRef<str>> {
         Self::markdown_events(&self.raw)
             .into_iter()
             .filter_map(|event| {
                 if let Event::Start(Tag::CodeBlock(kind)) = event
-                    && let pulldown_cmark::CodeBlockKind::Fenced(lang) = kind
-                    && lang != pulldown_cmark::CowStr::Borrowed("")
+                    && let pulldown_cmark::CodeBlockKind::Fenced(info) = kind
+                    && rendered_diagram(&info).is_none()
+                    && let Some(language) = CodeBlockInfo::parse(&info).language
                 {
-                    Some(Self::syntax_highlight_mapping(lang))
+                    Some(language)
                 } else {
                     None
                 }
             })
     }
+
+    /// Text of the content before the `<!-- more -->` marker, without formatting, with the
+    /// typography of the build
+    pub fn excerpt(&self, config: &BuildConfig) -> Option<String> {
+        let marker = pulldown_cmark::Parser::new_ext(&self.raw, Self::markdown_options())
+            .into_offset_iter()
+            .find_map(|(event, range)| match event {
+                Event::Html(html) | Event::InlineHtml(html) if is_excerpt_marker(&html) => {
+                    Some(range.start)
+                }
+                _ => None,
+            })?;
+
+        let mut ignore = false;
+        let mut excerpt = String::new();
+        for event in Self::typographic_markdown_events(&self.raw[..marker], config) {
+            match event {
+                Event::Start(Tag::CodeBlock(_) | Tag::MetadataBlock(_)) => ignore = true,
+                Event::End(TagEnd::CodeBlock | TagEnd::MetadataBlock(_)) => ignore = false,
+                Event::Text(text) | Event::Code(text) if !ignore => excerpt.push_str(&text),
+                Event::SoftBreak
+                | Event::HardBreak
+                | Event::End(
+                    TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::TableCell,
+                ) => excerpt.push(' '),
+                _ => {}
+            }
+        }
+
+        // Non-breaking spaces are kept
+        let excerpt = excerpt
+            .split_ascii_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        (!excerpt.is_empty()).then_some(excerpt)
+    }
+
+    /// Description from the metadata, falling back on the excerpt, with the typography of the
+    /// build
+    pub fn description(&self, config: &BuildConfig) -> Option<String> {
+        self.meta
+            .description()
+            .map(|description| config.typography.apply_to_plain_text(description))
+            .or_else(|| self.excerpt(config))
+    }
+
+    /// Number of words in the text of the content, without code blocks and metadata
+    pub fn word_count(&self) -> usize {
+        let mut ignore = false;
+        Self::markdown_events(&self.raw)
+            .into_iter()
+            .map(|event| match event {
+                Event::Start(Tag::CodeBlock(_) | Tag::MetadataBlock(_)) => {
+                    ignore = true;
+                    0
+                }
+                Event::End(TagEnd::CodeBlock | TagEnd::MetadataBlock(_)) => {
+                    ignore = false;
+                    0
+                }
+                Event::Text(text) if !ignore => text.split_whitespace().count(),
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Estimated reading time in minutes, at least one
+    pub fn reading_time(&self) -> usize {
+        self.word_count().div_ceil(WORDS_PER_MINUTE).max(1)
+    }
+
+    /// Whether the content has code blocks, other than diagrams
+    pub(crate) fn has_code_blocks(&self) -> bool {
+        Self::markdown_events(&self.raw)
+            .into_iter()
+            .any(|event| match event {
+                Event::Start(Tag::CodeBlock(pulldown_cmark::CodeBlockKind::Fenced(info))) => {
+                    rendered_diagram(&info).is_none()
+                }
+                Event::Start(Tag::CodeBlock(pulldown_cmark::CodeBlockKind::Indented)) => true,
+                _ => false,
+            })
+    }
+
+    /// Compile `rust` code blocks, and run them unless annotated otherwise
+    pub(crate) fn check_rust_code_blocks(&self) -> Result<(), GenerateHtmlError> {
+        let parser = pulldown_cmark::Parser::new_ext(&self.raw, Self::markdown_options());
+        // Code block being collected, with the line of its opening fence
+        let mut code_block: Option<(CodeBlockInfo, usize, String)> = None;
+        for (event, range) in parser.into_offset_iter() {
+            match event {
+                Event::Start(Tag::CodeBlock(pulldown_cmark::CodeBlockKind::Fenced(info))) => {
+                    let info = CodeBlockInfo::parse(&info);
+                    if rust_check::is_rust(&info) {
+                        let line = self.raw[..range.start].matches('\n').count() + 1;
+                        code_block = Some((info, line, String::new()));
+                    }
+                }
+                Event::Text(text) => {
+                    if let Some((_, _, code)) = code_block.as_mut() {
+                        code.push_str(&text);
+                    }
+                }
+                Event::End(TagEnd::CodeBlock) => {
+                    let Some((info, line, code)) = code_block.take() else {
+                        continue;
+                    };
+                    let code = self.code_block_code(&info, code)?;
+                    let mode = rust_check::Mode::from_info(&info);
+                    rust_check::check(&rust_check::program(&code), mode)
+                        .map_err(|e| GenerateHtmlError::RustCheck(self.path.clone(), line, e))?;
+                    println!(
+                        "Rust check: `{}:{line}` {}",
+                        self.path.display(),
+                        match mode {
+                            rust_check::Mode::Ignore => "ignored",
+                            _ => "passed",
+                        }
+                    );
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Diagrams of the content rendered to SVG files, with their file name
+    pub(crate) fn diagrams(&self) -> Result<Vec<(String, String)>, GenerateHtmlError> {
+        let mut diagrams = vec![];
+        let mut diagram: Option<(Diagram, CodeBlockInfo, String)> = None;
+        for event in Self::markdown_events(&self.raw) {
+            match event {
+                Event::Start(Tag::CodeBlock(pulldown_cmark::CodeBlockKind::Fenced(info))) => {
+                    diagram = rendered_diagram(&info)
+                        .map(|d| (d, CodeBlockInfo::parse(&info), String::new()));
+                }
+                Event::Text(text) => {
+                    if let Some((_, _, source)) = diagram.as_mut() {
+                        source.push_str(&text);
+                    }
+                }
+                Event::End(TagEnd::CodeBlock) => {
+                    if let Some((kind, info, source)) = diagram.take() {
+                        let source = self.code_block_code(&info, source)?;
+                        let svg = self.render_diagram(kind, &source)?;
+                        diagrams.push((diagram::file_name(diagrams.len() + 1), svg));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(diagrams)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum GenerateHtmlError {
-    #[error("unhandled markdown event: {0:?}")]
-    UnknownMarkdownEvent(Event<'static>),
+    #[error("Failed to highlight code block: {0}")]
+    SyntaxHighlight(syntect::Error),
+    #[error("Component `{}` in `{}`: {}", .1, .0.display(), .2)]
+    Component(PathBuf, String, ComponentError),
+    #[error("Component `{}` is not closed in `{}`", .1, .0.display())]
+    UnclosedComponent(PathBuf, String),
+    #[error("Raw HTML is not allowed in `{}`: `{}`", .0.display(), .1)]
+    RawHtml(PathBuf, String),
+    #[error("Failed to render diagram in `{}`: {}", .0.display(), .1)]
+    Diagram(PathBuf, String),
+    #[error("Failed to include `{}` in `{}`: {}", .1, .0.display(), .2)]
+    Include(PathBuf, String, IncludeError),
+    #[error("Rust code block at `{}:{}` {}", .0.display(), .1, .2)]
+    RustCheck(PathBuf, usize, String),
+    #[error("Wikilink `[[{}]]` in `{}` does not match the title or slug of any content", .1, .0.display())]
+    UnresolvedWikilink(PathBuf, String),
 }
 
 impl Content {
-    pub fn generate_html(&self) -> Result<String, GenerateHtmlError> {
-        let markdown_events = Content::markdown_events(&self.raw);
+    pub fn generate_html(&self, config: &BuildConfig) -> Result<String, GenerateHtmlError> {
+        self.render_markdown(config, &self.raw)
+    }
+
+    /// Render markdown of the content, or children of a component located in the content
+    fn render_markdown(
+        &self,
+        config: &BuildConfig,
+        markdown: &str,
+    ) -> Result<String, GenerateHtmlError> {
+        let markdown_events = Content::typographic_markdown_events(markdown, config);
         let mut ignore = false;
 
         // table helpers
@@ -200,8 +748,35 @@ impl Content {
         let mut table_cell_idx = 0;
         let mut table_state = TableState::Head;
 
-        // Image helper
-        let mut inside_image = false;
+        // Image helpers: image being rendered with its alt text, and a figure replacing its
+        // paragraph when the image is alone in it
+        struct Image {
+            dest_url: String,
+            title: String,
+            alt: String,
+        }
+        let mut image: Option<Image> = None;
+        // Position of the current paragraph: number of views, start of its tag and of its content
+        let mut paragraph_start = (0, 0, 0);
+        // Figure with the position where its image ends, used if nothing follows it
+        let mut figure: Option<(usize, usize, String)> = None;
+
+        // Whether the current link points outside of the website
+        let mut external_link = false;
+
+        // Code block helper: attributes and code, highlighted once the block is complete
+        let mut code_block: Option<(CodeBlockInfo, String)> = None;
+
+        // Footnotes helpers: labels in order of first reference, with their number of references
+        let mut footnote_references: Vec<(String, usize)> = vec![];
+        let mut footnote_definitions: Vec<(String, String)> = vec![];
+        let mut footnote_definition_start = None;
+
+        // HTML block, emitted line by line
+        let mut html_block: Option<String> = None;
+
+        // Components with children in following blocks, with the number of views before them
+        let mut open_components: Vec<(Component, Attributes, usize)> = vec![];
 
         // List of views ready to be used
         let mut views = vec![];
@@ -211,27 +786,19 @@ impl Content {
 
         for event in markdown_events {
             match (event, ignore) {
+                // image alt text, without formatting
+                (Event::Text(text) | Event::Code(text), false) if image.is_some() => {
+                    if let Some(image) = image.as_mut() {
+                        image.alt.push_str(text.as_ref());
+                    }
+                }
+
                 // text
                 (Event::Text(text), false) => {
-                    if inside_image {
-                        current_view.push_str(
-                            format!(
-                                "<blockquote class=\"{}\">{}</blockquote>",
-                                tw_join!(
-                                    "p-4",
-                                    "mb-4",
-                                    "border-l-8",
-                                    "border-solid",
-                                    "border-gray-500",
-                                    "bg-gray-400",
-                                    "dark:bg-gray-800"
-                                ),
-                                text.as_ref()
-                            )
-                            .as_ref(),
-                        );
+                    if let Some((_info, code)) = code_block.as_mut() {
+                        code.push_str(text.as_ref());
                     } else {
-                        current_view.push_str(text.as_ref());
+                        current_view.push_str(escape_html(text.as_ref()).as_ref());
                     }
                 }
 
@@ -277,15 +844,29 @@ impl Content {
                 }
 
                 // paragraph
-                (Event::Start(Tag::Paragraph), false) => current_view.push_str(
-                    format!(
-                        "<p class=\"{}\">",
-                        tw_join!("my-2", "text-lg", "text-justify")
-                    )
-                    .as_ref(),
-                ),
+                (Event::Start(Tag::Paragraph), false) => {
+                    let start = current_view.len();
+                    current_view.push_str(
+                        format!(
+                            "<p class=\"{}\">",
+                            tw_join!("my-2", "text-lg", "text-justify")
+                        )
+                        .as_ref(),
+                    );
+                    paragraph_start = (views.len(), start, current_view.len());
+                }
                 (Event::End(TagEnd::Paragraph), false) => {
-                    current_view.push_str("</p>\n");
+                    match figure.take() {
+                        // The paragraph only contains an image with a title, replace it with a figure
+                        Some((views_len, current_view_len, figure))
+                            if views_len == views.len()
+                                && current_view_len == current_view.len() =>
+                        {
+                            current_view.truncate(paragraph_start.1);
+                            current_view.push_str(&figure);
+                        }
+                        _ => current_view.push_str("</p>\n"),
+                    }
                     views.push(current_view.clone());
                     current_view.clear();
                 }
@@ -295,37 +876,132 @@ impl Content {
                     Event::Start(Tag::Image {
                         link_type: _link_type,
                         dest_url,
-                        title: _title,
+                        title,
                         id: _id,
                     }),
                     false,
                 ) => {
-                    inside_image = true;
-                    current_view.push_str(
-                        format!(
-                            "<img loading=\"lazy\" src={dest_url} class=\"{}\" />",
-                            tw_join!("my-4")
-                        )
-                        .as_ref(),
-                    );
+                    image = Some(Image {
+                        dest_url: dest_url.to_string(),
+                        title: title.to_string(),
+                        alt: String::new(),
+                    });
                 }
-                (Event::End(TagEnd::Image), _) => {
-                    inside_image = false;
+                (Event::End(TagEnd::Image), false) => {
+                    if let Some(image) = image.take() {
+                        let first_in_paragraph = paragraph_start.0 == views.len()
+                            && paragraph_start.2 == current_view.len();
+                        let path = self.local_image_path(&image.dest_url);
+                        let size = path.as_deref().and_then(Self::image_size);
+                        let size_attributes = size
+                            .map(|(width, height)| {
+                                format!(" width=\"{width}\" height=\"{height}\"")
+                            })
+                            .unwrap_or_default();
+                        let variants = match (config.responsive_images, path.as_deref()) {
+                            (Some(responsive_images), Some(path)) => {
+                                crate::responsive_images::variants(path, &responsive_images)
+                            }
+                            _ => vec![],
+                        };
+                        let placeholder_attributes = config
+                            .blurhash
+                            .and(path.as_deref())
+                            .and_then(crate::image_placeholder::placeholder)
+                            .map(|placeholder| placeholder.attributes())
+                            .unwrap_or_default();
+                        let src = escape_html(&image.dest_url);
+                        let img = |class: &str, title: &str| {
+                            let attributes = format!(
+                                " loading=\"lazy\" alt=\"{}\"{title}{size_attributes}{placeholder_attributes} class=\"{class}\"",
+                                escape_html(&image.alt),
+                            );
+                            match config.responsive_images {
+                                Some(responsive_images) if !variants.is_empty() => {
+                                    crate::responsive_images::picture(
+                                        &src,
+                                        &attributes,
+                                        &variants,
+                                        size.map(|(width, _)| width),
+                                        responsive_images.sizes,
+                                        &responsive_images,
+                                    )
+                                }
+                                _ => format!("<img src=\"{src}\"{attributes} />"),
+                            }
+                        };
+
+                        match image.title.as_ref() {
+                            "" => current_view.push_str(&img(&tw_join!("my-4", "h-auto"), "")),
+                            title => {
+                                let title = escape_html(title);
+                                current_view.push_str(&img(
+                                    &tw_join!("my-4", "h-auto"),
+                                    &format!(" title=\"{title}\""),
+                                ));
+
+                                if first_in_paragraph {
+                                    figure = Some((
+                                        views.len(),
+                                        current_view.len(),
+                                        format!(
+                                            "<figure class=\"{}\">{}<figcaption class=\"{}\">{title}</figcaption></figure>\n",
+                                            tw_join!("my-4"),
+                                            img(&tw_join!("h-auto"), ""),
+                                            tw_join!(
+                                                "mt-2",
+                                                "text-center",
+                                                "text-gray-600",
+                                                "dark:text-gray-400"
+                                            )
+                                        ),
+                                    ));
+                                }
+                            }
+                        }
+                    }
                 }
+                // ignore formatting inside alt text of images
+                (_, false) if image.is_some() => {}
 
                 // links
                 (
                     Event::Start(Tag::Link {
-                        link_type: _link_type,
+                        link_type,
                         dest_url,
                         title: _title,
                         id: _id,
                     }),
                     false,
                 ) => {
+                    // Wikilinks point to other content
+                    let dest_url = match link_type {
+                        pulldown_cmark::LinkType::WikiLink { .. } => {
+                            let slug = self.wikilink(&dest_url).ok_or(
+                                GenerateHtmlError::UnresolvedWikilink(
+                                    self.path.clone(),
+                                    dest_url.to_string(),
+                                ),
+                            )?;
+                            format!("{}{slug}", config.base_url).into()
+                        }
+                        _ => dest_url,
+                    };
+                    let policy = config.external_link_policy(&dest_url);
+                    external_link = policy.is_some();
+                    let external_attributes = policy
+                        .map(|policy| {
+                            let target = policy
+                                .target
+                                .map(|target| format!(" target=\"{}\"", escape_html(target)))
+                                .unwrap_or_default();
+                            format!(" rel=\"{}\"{target}", policy.rel())
+                        })
+                        .unwrap_or_default();
                     current_view.push_str(
                         format!(
-                            "<a href=\"{dest_url}\" class=\"{}\">",
+                            "<a href=\"{}\"{external_attributes} class=\"{}\">",
+                            escape_html(&dest_url),
                             tw_join!(
                                 "underline",
                                 "text-sky-900",
@@ -337,6 +1013,11 @@ impl Content {
                     );
                 }
                 (Event::End(TagEnd::Link), _) => {
+                    if std::mem::take(&mut external_link) && config.external_links.icon {
+                        current_view.push_str(&leptos::prelude::RenderHtml::to_html(
+                            crate::html::external_link_icon(),
+                        ));
+                    }
                     current_view.push_str("</a>");
                     views.push(current_view.clone());
                     current_view.clear();
@@ -400,13 +1081,48 @@ impl Content {
                     current_view.clear();
                 }
 
+                // strikethrough
+                (Event::Start(Tag::Strikethrough), false) => {
+                    current_view
+                        .push_str(format!("<del class=\"{}\">", tw_join!("line-through")).as_ref());
+                }
+                (Event::End(TagEnd::Strikethrough), false) => {
+                    current_view.push_str("</del>");
+                    views.push(current_view.clone());
+                    current_view.clear();
+                }
+
+                // superscript
+                (Event::Start(Tag::Superscript), false) => {
+                    current_view.push_str(
+                        format!("<sup class=\"{}\">", tw_join!("align-super", "text-sm")).as_ref(),
+                    );
+                }
+                (Event::End(TagEnd::Superscript), false) => {
+                    current_view.push_str("</sup>");
+                    views.push(current_view.clone());
+                    current_view.clear();
+                }
+
+                // subscript
+                (Event::Start(Tag::Subscript), false) => {
+                    current_view.push_str(
+                        format!("<sub class=\"{}\">", tw_join!("align-sub", "text-sm")).as_ref(),
+                    );
+                }
+                (Event::End(TagEnd::Subscript), false) => {
+                    current_view.push_str("</sub>");
+                    views.push(current_view.clone());
+                    current_view.clear();
+                }
+
                 // code inline
                 (Event::Code(code), false) => {
                     current_view.push_str(
                         format!(
                             "<code class=\"{}\">{}</code>",
                             tw_join!("font-mono", "bg-white", "text-black", "px-1", "py-0.5"),
-                            code.as_ref(),
+                            escape_html(code.as_ref()),
                         )
                         .as_ref(),
                     );
@@ -414,6 +1130,21 @@ impl Content {
                     current_view.clear();
                 }
 
+                // math
+                (Event::InlineMath(tex), false) => {
+                    current_view.push_str(math::to_mathml(&tex, false, "").as_ref());
+                }
+                (Event::DisplayMath(tex), false) => {
+                    current_view.push_str(
+                        math::to_mathml(
+                            &tex,
+                            true,
+                            &tw_join!("my-4", "overflow-x-auto", "text-xl"),
+                        )
+                        .as_ref(),
+                    );
+                }
+
                 // line break
                 (Event::HardBreak, false) => {
                     current_view.push_str("<br />");
@@ -422,34 +1153,62 @@ impl Content {
                 }
 
                 // code block
-                (Event::Start(Tag::CodeBlock(code_block)), false) => {
-                    let class_code_language = match code_block {
-                        pulldown_cmark::CodeBlockKind::Indented
-                        | pulldown_cmark::CodeBlockKind::Fenced(
-                            pulldown_cmark::CowStr::Borrowed(""),
-                        ) => "".into(),
-                        pulldown_cmark::CodeBlockKind::Fenced(language) => {
-                            let language = Self::syntax_highlight_mapping(language);
-                            format!(" class=\"language-{language}\"")
-                        }
+                (Event::Start(Tag::CodeBlock(kind)), false) => {
+                    let info = match kind {
+                        pulldown_cmark::CodeBlockKind::Indented => CodeBlockInfo::default(),
+                        pulldown_cmark::CodeBlockKind::Fenced(info) => CodeBlockInfo::parse(&info),
                     };
-                    current_view.push_str(
-                        format!(
-                            "<pre class=\"{}\"><code{}>",
-                            tw_join!(
-                                "overflow-x-scroll",
-                                "font-mono",
-                                "bg-white",
-                                "text-black",
-                                "p-4",
-                            ),
-                            class_code_language
-                        )
-                        .as_ref(),
-                    );
+                    code_block = Some((info, String::new()));
                 }
                 (Event::End(TagEnd::CodeBlock), false) => {
-                    current_view.push_str("</code></pre>");
+                    if let Some((info, code)) = code_block.take() {
+                        let code = self.code_block_code(&info, code)?;
+                        let diagram = info.language.as_deref().and_then(Diagram::from_language);
+                        match diagram {
+                            Some(diagram) if diagram.has_renderer() => {
+                                let svg = self.render_diagram(diagram, &code)?;
+                                current_view.push_str(
+                                    format!(
+                                        "<div class=\"{}\" role=\"img\" aria-label=\"Diagram\" data-diagram=\"{}\">{}</div>",
+                                        tw_join!("overflow-x-auto", "bg-white", "p-4", "my-4"),
+                                        escape_html(info.language.as_deref().unwrap_or_default()),
+                                        diagram::inline(&svg)
+                                    )
+                                    .as_ref(),
+                                );
+                            }
+                            _ => {
+                                if let Some(diagram) = diagram {
+                                    println!(
+                                        "Diagram: no renderer for {diagram:?} in `{}`, showing its source",
+                                        self.path.display()
+                                    );
+                                }
+                                // Rust code blocks are shown like doc tests once checked
+                                let (code, playground_url) = match config.rust_check {
+                                    Some(rust_check) if rust_check::is_rust(&info) => {
+                                        let playground_url =
+                                            match rust_check::Mode::from_info(&info) {
+                                                rust_check::Mode::Ignore
+                                                | rust_check::Mode::CompileFail => None,
+                                                _ => rust_check.playground_link.then(|| {
+                                                    rust_check::playground_url(
+                                                        &rust_check::program(&code),
+                                                    )
+                                                }),
+                                            };
+                                        (rust_check::visible(&code), playground_url)
+                                    }
+                                    _ => (code, None),
+                                };
+                                current_view.push_str(
+                                    code_block::render(&info, &code, playground_url.as_deref())
+                                        .map_err(GenerateHtmlError::SyntaxHighlight)?
+                                        .as_ref(),
+                                );
+                            }
+                        }
+                    }
                     views.push(current_view.clone());
                     current_view.clear();
                 }
@@ -586,8 +1345,18 @@ impl Content {
                     current_view.clear();
                 }
 
+                // admonitions
+                (Event::Start(Tag::BlockQuote(Some(kind))), false) => {
+                    current_view.push_str(Self::admonition_start(kind).as_ref());
+                }
+                (Event::End(TagEnd::BlockQuote(Some(_kind))), false) => {
+                    current_view.push_str("</aside>");
+                    views.push(current_view.clone());
+                    current_view.clear();
+                }
+
                 // quotes
-                (Event::Start(Tag::BlockQuote(_kind)), false) => {
+                (Event::Start(Tag::BlockQuote(None)), false) => {
                     current_view.push_str(
                         format!(
                             "<blockquote class=\"{}\">",
@@ -604,12 +1373,54 @@ impl Content {
                         .as_ref(),
                     );
                 }
-                (Event::End(TagEnd::BlockQuote(_kind)), false) => {
+                (Event::End(TagEnd::BlockQuote(None)), false) => {
                     current_view.push_str("</blockquote>");
                     views.push(current_view.clone());
                     current_view.clear();
                 }
 
+                // definition lists
+                (Event::Start(Tag::DefinitionList), false) => {
+                    current_view.push_str(format!("<dl class=\"{}\">", tw_join!("my-4")).as_ref());
+                }
+                (Event::End(TagEnd::DefinitionList), false) => {
+                    current_view.push_str("</dl>");
+                    views.push(current_view.clone());
+                    current_view.clear();
+                }
+
+                // definition list titles
+                (Event::Start(Tag::DefinitionListTitle), false) => {
+                    current_view.push_str(
+                        format!(
+                            "<dt class=\"{}\">",
+                            tw_join!("mt-2", "text-lg", "font-bold")
+                        )
+                        .as_ref(),
+                    );
+                }
+                (Event::End(TagEnd::DefinitionListTitle), false) => {
+                    current_view.push_str("</dt>");
+                    views.push(current_view.clone());
+                    current_view.clear();
+                }
+
+                // definition list definitions
+                (Event::Start(Tag::DefinitionListDefinition), false) => {
+                    current_view.push_str(
+                        format!(
+                            "<dd class=\"{}\">",
+                            tw_join!("ml-4", "pl-4", "text-lg", "text-justify")
+                        )
+                        .as_ref(),
+                    );
+                }
+                (Event::End(TagEnd::DefinitionListDefinition), false) => {
+                    current_view.push_str("</dd>");
+                    views.push(current_view.clone());
+                    current_view.clear();
+                }
+
                 // checkboxes
                 (Event::TaskListMarker(checked), false) => {
                     let checked = if checked { "checked" } else { "" };
@@ -631,135 +1442,1098 @@ impl Content {
                     current_view.clear();
                 }
 
-                // html blocks
-                (Event::Start(Tag::HtmlBlock), false) => {} // noop
-                (Event::End(TagEnd::HtmlBlock), false) => {} // noop
-
-                // html
-                (Event::Html(html), false) => {
-                    let dom = match tl::parse(html.as_ref(), tl::ParserOptions::default()) {
-                        Ok(dom) => dom,
-                        Err(e) => {
-                            println!("Failed to parse HTML: `{}`: {e}", html.as_ref());
-                            continue;
+                // footnote references
+                (Event::FootnoteReference(label), false) => {
+                    let label = label.to_string();
+                    let (number, reference) = match footnote_references
+                        .iter_mut()
+                        .enumerate()
+                        .find(|(_, (existing, _))| *existing == label)
+                    {
+                        Some((idx, (_, count))) => {
+                            *count += 1;
+                            (idx + 1, *count)
+                        }
+                        None => {
+                            footnote_references.push((label.clone(), 1));
+                            (footnote_references.len(), 1)
                         }
                     };
 
-                    struct CustomComponent {
-                        tag: &'static str,
-                        attribute: &'static str,
-                        process: fn(&Content, &str) -> leptos::prelude::AnyView,
+                    current_view.push_str(
+                        format!(
+                            "<sup id=\"{}\"><a href=\"#{}\" class=\"{}\">{number}</a></sup>",
+                            Self::footnote_reference_id(&label, reference),
+                            Self::footnote_definition_id(&label),
+                            tw_join!("underline", "text-sky-900", "dark:text-yellow-400")
+                        )
+                        .as_ref(),
+                    );
+                }
+
+                // footnote definitions, collected and rendered at the end of the content
+                (Event::Start(Tag::FootnoteDefinition(label)), false) => {
+                    if !current_view.is_empty() {
+                        views.push(current_view.clone());
+                        current_view.clear();
+                    }
+                    footnote_definition_start = Some((label.to_string(), views.len()));
+                }
+                (Event::End(TagEnd::FootnoteDefinition), false) => {
+                    // Label is not present on the end event, it was kept from the start event
+                    if let Some((label, start)) = footnote_definition_start.take() {
+                        let mut definition = views.drain(start..).collect::<Vec<_>>().join("");
+                        definition.push_str(&current_view);
+                        current_view.clear();
+
+                        footnote_definitions.push((label, definition));
                     }
+                }
 
-                    fn process_image_grid(
-                        content: &Content,
-                        attribute: &str,
-                    ) -> leptos::prelude::AnyView {
-                        use leptos::prelude::*;
-
-                        let assets = if content.assets.is_some() {
-                            // There are some assets, they are located in the parent directory
-                            content.path.parent().unwrap()
-                        } else {
-                            // If there is no assets, then there is no html to render
-                            return ().into_any();
-                        };
+                // html blocks, processed once complete
+                (Event::Start(Tag::HtmlBlock), false) => html_block = Some(String::new()),
+                (Event::Html(html), false) => match html_block.as_mut() {
+                    Some(html_block) => html_block.push_str(html.as_ref()),
+                    None => current_view
+                        .push_str(self.apply_raw_html_policy(config, &html, false)?.as_ref()),
+                },
+                (Event::End(TagEnd::HtmlBlock), false) => {
+                    let html = html_block.take().unwrap_or_default();
+
+                    // Closing tag of a component, its children were rendered since its opening tag
+                    if open_components.last().is_some_and(|(component, _, _)| {
+                        html.trim() == format!("</{}>", component.tag)
+                    }) {
+                        if let Some((component, attributes, start)) = open_components.pop() {
+                            let mut children = views.drain(start..).collect::<Vec<_>>().join("");
+                            children.push_str(&current_view);
+                            current_view.clear();
+
+                            let view = self.render_component(
+                                &component,
+                                &attributes,
+                                Some(&children),
+                                config,
+                            )?;
+                            views.push(view);
+                        }
+                        continue;
+                    }
 
-                        let directory = assets.join(attribute);
-
-                        // Collect list of images
-                        let mut list_images = walkdir::WalkDir::new(&directory)
-                            .into_iter()
-                            .filter_map(|e| e.ok())
-                            .map(|dir_entry| dir_entry.into_path())
-                            .filter(|path| path.is_file())
-                            // Get relative path to be accepted in the html
-                            .filter_map(|path| {
-                                path.strip_prefix(assets)
-                                    .map(|path| path.to_path_buf())
-                                    .ok()
-                            })
-                            .collect::<Vec<_>>();
+                    match crate::components::find(config, &html) {
+                        Some((component, attributes)) => {
+                            match ComponentTag::find(&html, component.tag) {
+                                ComponentTag::SelfClosing => {
+                                    current_view.push_str(&self.render_component(
+                                        &component,
+                                        &attributes,
+                                        None,
+                                        config,
+                                    )?)
+                                }
+                                ComponentTag::Closed(children) => {
+                                    let children = self.render_markdown(config, children)?;
+                                    current_view.push_str(&self.render_component(
+                                        &component,
+                                        &attributes,
+                                        Some(&children),
+                                        config,
+                                    )?);
+                                }
+                                // Children are in the following blocks, until the closing tag
+                                ComponentTag::Open(children) => {
+                                    if !current_view.is_empty() {
+                                        views.push(current_view.clone());
+                                        current_view.clear();
+                                    }
+                                    open_components.push((component, attributes, views.len()));
+                                    // Children can start in the block of the opening tag
+                                    if !children.trim().is_empty() {
+                                        views.push(self.render_markdown(config, children)?);
+                                    }
+                                    continue;
+                                }
+                            }
+                        }
+                        None => current_view
+                            .push_str(self.apply_raw_html_policy(config, &html, false)?.as_ref()),
+                    }
+                    views.push(current_view.clone());
+                    current_view.clear();
+                }
+
+                // inline html
+                (Event::InlineHtml(html), false) => {
+                    current_view
+                        .push_str(self.apply_raw_html_policy(config, &html, true)?.as_ref());
+                }
 
-                        // Sort by name
-                        list_images.sort();
+                // ignored events
+                (_, true) => {} // noop
+            }
+        }
 
-                        println!("ImageGrid: Files retained: {list_images:#?}");
+        if let Some((component, _, _)) = open_components.first() {
+            return Err(GenerateHtmlError::UnclosedComponent(
+                self.path.clone(),
+                component.tag.to_string(),
+            ));
+        }
 
-                        // For each image, create html view
-                        let list_images = list_images.into_iter().map(|path| {
-                            let filename = path.file_name().and_then(|file| file.to_str()).map(|file| file.to_string()).unwrap();
-                            let path = path.to_str().unwrap();
+        // Footnotes section, at the end of the content
+        if !footnote_definitions.is_empty() {
+            views.push(Self::footnotes_section(
+                &footnote_references,
+                footnote_definitions,
+            ));
+        }
 
-                            view! {
-                                    <a class=tw_join!("w-full", "h-full", "border-2", "border-dashed", "border-sky-900", "dark:border-yellow-600") href={path.to_string()}>
-                                        <img loading="lazy" class=tw_join!("h-auto", "max-w-32") src={path.to_string()} alt=filename />
-                                    </a>
-                            }
-                        }).collect_view();
+        // Do not add any whitespace between views, they can be interpreted as a space in web
+        // browsers, causing unwanted visual effects.
+        let html = views.join("");
 
-                        // Final view with images
-                        leptos::view! {
-                            <div data-testid="ImageGrid" class=tw_join!("my-4", "grid", "grid-cols-2", "gap-5")>
-                                {list_images}
-                            </div>
-                        }
-                        .into_any()
-                    }
+        Ok(html)
+    }
 
-                    const CUSTOM_COMPONENTS: &[CustomComponent] = &[CustomComponent {
-                        tag: "ImageGrid",
-                        attribute: "src",
-                        process: process_image_grid,
-                    }];
-
-                    let mut view = None;
-
-                    for custom_tag_name in CUSTOM_COMPONENTS {
-                        let tag = match dom
-                            .nodes()
-                            .iter()
-                            .find(|node| {
-                                node.as_tag()
-                                    .is_some_and(|tag| tag.name() == custom_tag_name.tag)
-                            })
-                            // Coerce as an html tag
-                            .and_then(|node| node.as_tag())
-                        {
-                            Some(tag) => tag,
-                            None => continue,
-                        };
+    /// Render a component to HTML
+    fn render_component(
+        &self,
+        component: &Component,
+        attributes: &Attributes,
+        children: Option<&str>,
+        config: &BuildConfig,
+    ) -> Result<String, GenerateHtmlError> {
+        (component.render)(attributes, children, self, config)
+            .map(leptos::prelude::RenderHtml::to_html)
+            .map_err(|e| {
+                GenerateHtmlError::Component(self.path.clone(), component.tag.to_string(), e)
+            })
+    }
+
+    /// Code of a block, read from the assets of the content when it includes a file
+    fn code_block_code(
+        &self,
+        info: &CodeBlockInfo,
+        code: String,
+    ) -> Result<String, GenerateHtmlError> {
+        let Some(file) = info.get("file") else {
+            return Ok(code);
+        };
+        include::read(
+            self.assets_directory(),
+            file,
+            info.get("region"),
+            info.get("lines"),
+        )
+        .map_err(|e| GenerateHtmlError::Include(self.path.clone(), file.to_string(), e))
+    }
+
+    /// Render a diagram to SVG, once for the page and the file of the diagram
+    fn render_diagram(&self, diagram: Diagram, source: &str) -> Result<String, GenerateHtmlError> {
+        let mut rendered_diagrams = self
+            .rendered_diagrams
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let key = (diagram, source.to_string());
+        if let Some(svg) = rendered_diagrams.get(&key) {
+            return Ok(svg.clone());
+        }
+
+        let svg = diagram
+            .render(source)
+            .map_err(|e| GenerateHtmlError::Diagram(self.path.clone(), e))?;
+        rendered_diagrams.insert(key, svg.clone());
+        Ok(svg)
+    }
+
+    /// Apply the raw HTML policy to HTML written in the content
+    fn apply_raw_html_policy(
+        &self,
+        config: &BuildConfig,
+        html: &str,
+        inline: bool,
+    ) -> Result<String, GenerateHtmlError> {
+        // The excerpt marker is not part of the page
+        if is_excerpt_marker(html) {
+            return Ok(String::new());
+        }
+
+        match config.raw_html {
+            RawHtml::Drop => Ok(String::new()),
+            RawHtml::Passthrough => Ok(html.to_string()),
+            RawHtml::Sanitize if inline => Ok(sanitize::inline(html)),
+            RawHtml::Sanitize => Ok(sanitize::block(html)),
+            RawHtml::Reject => Err(GenerateHtmlError::RawHtml(
+                self.path.clone(),
+                html.trim().to_string(),
+            )),
+        }
+    }
+
+    /// Path of an image located in the assets of the content
+    fn local_image_path(&self, dest_url: &str) -> Option<PathBuf> {
+        // Only images next to the content are copied with it
+        self.assets.as_ref()?;
+        if dest_url.contains("://") || dest_url.starts_with('/') || dest_url.starts_with("data:") {
+            return None;
+        }
+
+        let path = dest_url.split(['?', '#']).next()?;
+        self.path.parent().map(|parent| parent.join(path))
+    }
+
+    /// Width and height of an image
+    pub(crate) fn image_size(path: &Path) -> Option<(usize, usize)> {
+        if path.extension().is_some_and(|ext| ext == "svg") {
+            return Self::svg_size(path);
+        }
+
+        match imagesize::size(path) {
+            Ok(size) => Some((size.width, size.height)),
+            Err(e) => {
+                println!("Image: could not get size of `{}`: {e}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Size of a SVG image, from the attributes of its root element
+    fn svg_size(path: &Path) -> Option<(usize, usize)> {
+        let svg = std::fs::read_to_string(path).ok()?;
+        let dom = tl::parse(&svg, tl::ParserOptions::default()).ok()?;
+        let tag = dom.nodes().iter().find_map(|node| {
+            node.as_tag()
+                .filter(|tag| tag.name().as_utf8_str().eq_ignore_ascii_case("svg"))
+        })?;
+        let attribute = |name: &str| {
+            tag.attributes()
+                .get(name)
+                .flatten()
+                .map(|value| value.as_utf8_str().to_string())
+        };
+        let length = |value: String| {
+            value
+                .trim()
+                .trim_end_matches("px")
+                .parse::<f64>()
+                .ok()
+                .map(|value| value.round() as usize)
+        };
+
+        if let Some(width) = attribute("width").and_then(length)
+            && let Some(height) = attribute("height").and_then(length)
+        {
+            return Some((width, height));
+        }
+
+        // Fallback on the dimensions of the view box: `min-x min-y width height`
+        let view_box = attribute("viewBox")?;
+        let mut view_box = view_box
+            .split([' ', ','])
+            .filter(|value| !value.is_empty())
+            .skip(2)
+            .map(|value| length(value.into()));
+        Some((view_box.next()??, view_box.next()??))
+    }
+
+    /// Label of GitHub-style admonitions
+    fn admonition_label(kind: pulldown_cmark::BlockQuoteKind) -> &'static str {
+        match kind {
+            pulldown_cmark::BlockQuoteKind::Note => "Note",
+            pulldown_cmark::BlockQuoteKind::Tip => "Tip",
+            pulldown_cmark::BlockQuoteKind::Important => "Important",
+            pulldown_cmark::BlockQuoteKind::Warning => "Warning",
+            pulldown_cmark::BlockQuoteKind::Caution => "Caution",
+        }
+    }
+
+    /// Opening tag of GitHub-style admonitions, with their title
+    fn admonition_start(kind: pulldown_cmark::BlockQuoteKind) -> String {
+        use crate::html::prelude::*;
+
+        let (icon, class, title_class) = match kind {
+            pulldown_cmark::BlockQuoteKind::Note => (
+                icon_information_circle(None).into_any(),
+                tw_join!("border-sky-600", "bg-sky-100", "dark:bg-sky-950"),
+                tw_join!("text-sky-800", "dark:text-sky-300"),
+            ),
+            pulldown_cmark::BlockQuoteKind::Tip => (
+                icon_light_bulb(None).into_any(),
+                tw_join!("border-green-600", "bg-green-100", "dark:bg-green-950"),
+                tw_join!("text-green-800", "dark:text-green-300"),
+            ),
+            pulldown_cmark::BlockQuoteKind::Important => (
+                icon_exclamation_circle(None).into_any(),
+                tw_join!("border-violet-600", "bg-violet-100", "dark:bg-violet-950"),
+                tw_join!("text-violet-800", "dark:text-violet-300"),
+            ),
+            pulldown_cmark::BlockQuoteKind::Warning => (
+                icon_exclamation_triangle(None).into_any(),
+                tw_join!("border-amber-500", "bg-amber-100", "dark:bg-amber-950"),
+                tw_join!("text-amber-800", "dark:text-amber-300"),
+            ),
+            pulldown_cmark::BlockQuoteKind::Caution => (
+                icon_shield_exclamation(None).into_any(),
+                tw_join!("border-red-600", "bg-red-100", "dark:bg-red-950"),
+                tw_join!("text-red-800", "dark:text-red-300"),
+            ),
+        };
+        let label = Self::admonition_label(kind);
+
+        format!(
+            "<aside role=\"note\" aria-label=\"{label}\" class=\"{}\"><p class=\"{}\"><span aria-hidden=\"true\">{}</span>{label}</p>",
+            tw_join!("p-4", "my-4", "border-l-8", "border-solid", class),
+            tw_join!(
+                "flex",
+                "items-center",
+                "gap-2",
+                "text-lg",
+                "font-bold",
+                title_class
+            ),
+            RenderHtml::to_html(icon),
+        )
+    }
+
+    fn footnote_definition_id(label: &str) -> String {
+        format!("fn-{}", slug::slugify(label))
+    }
+
+    fn footnote_reference_id(label: &str, reference: usize) -> String {
+        match reference {
+            1 => format!("fnref-{}", slug::slugify(label)),
+            reference => format!("fnref-{}-{reference}", slug::slugify(label)),
+        }
+    }
+
+    /// Render footnote definitions in the order they were referenced, with links back to every
+    /// reference. Definitions that were never referenced are added at the end.
+    fn footnotes_section(
+        references: &[(String, usize)],
+        mut definitions: Vec<(String, String)>,
+    ) -> String {
+        let mut ordered = vec![];
+        for (number, (label, count)) in references.iter().enumerate() {
+            if let Some(idx) = definitions.iter().position(|(el, _)| el == label) {
+                let (label, definition) = definitions.remove(idx);
+                ordered.push((number + 1, label, definition, *count));
+            }
+        }
+        let mut number = references.len();
+        for (label, definition) in definitions {
+            number += 1;
+            ordered.push((number, label, definition, 0));
+        }
 
-                        let attribute = match tag.attributes().get(custom_tag_name.attribute) {
-                            Some(Some(attribute)) => attribute,
-                            _ => continue,
+        let items = ordered
+            .into_iter()
+            .map(|(number, label, mut definition, count)| {
+                let back_links = (1..=count)
+                    .map(|reference| {
+                        // Number the back links when there are multiple references
+                        let suffix = match count {
+                            1 => String::new(),
+                            _ => format!("<sup>{reference}</sup>"),
                         };
+                        format!(
+                            " <a href=\"#{}\" aria-label=\"Back to reference {number}\" class=\"{}\">↩{suffix}</a>",
+                            Self::footnote_reference_id(&label, reference),
+                            tw_join!("underline", "text-sky-900", "dark:text-yellow-400"),
+                        )
+                    })
+                    .collect::<String>();
 
-                        let attribute = attribute.as_utf8_str();
-                        view = Some((custom_tag_name.process)(self, attribute.as_ref()));
-                    }
+                // Put back links at the end of the last paragraph of the definition
+                match definition.rfind("</p>") {
+                    Some(idx) => definition.insert_str(idx, &back_links),
+                    None => definition.push_str(&back_links),
+                }
 
-                    if let Some(view) = view {
-                        current_view.push_str(leptos::prelude::RenderHtml::to_html(view).as_ref());
-                        views.push(current_view.clone());
-                        current_view.clear();
-                    }
+                format!(
+                    "<li id=\"{}\" value=\"{number}\">{definition}</li>",
+                    Self::footnote_definition_id(&label)
+                )
+            })
+            .collect::<String>();
+
+        format!(
+            "<section aria-label=\"Footnotes\" class=\"{}\"><ol class=\"{}\">{items}</ol></section>",
+            tw_join!("mt-8", "pt-4", "border-t", "border-gray-500", "text-sm"),
+            tw_join!("ml-4", "pl-4", "list-decimal")
+        )
+    }
+}
+
+fn is_excerpt_marker(html: &str) -> bool {
+    html.trim() == EXCERPT_MARKER
+}
+
+/// Diagram drawn from a code block, from its info string
+fn rendered_diagram(info: &str) -> Option<Diagram> {
+    CodeBlockInfo::parse(info)
+        .language
+        .and_then(|language| Diagram::from_language(&language))
+        .filter(Diagram::has_renderer)
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ExternalLinks, Styles};
+
+    /// Markdown escapes, making pulldown-cmark emit the script tag as text
+    const HOSTILE_TEXT: &str = r"\<script\>alert(1)\</script\>";
+    const ESCAPED_TEXT: &str = "&lt;script&gt;alert(1)&lt;/script&gt;";
+    /// Closes the attribute it is in, and adds an event handler
+    const HOSTILE_ATTRIBUTE: &str = r#"x" onerror="alert(1)"#;
+    const ESCAPED_ATTRIBUTE: &str = "x&quot; onerror=&quot;alert(1)";
+
+    fn config() -> BuildConfig<'static> {
+        BuildConfig::new(
+            "https://example.com",
+            "/",
+            0,
+            Styles {
+                website: "style.css",
+                #[cfg(feature = "opengraph")]
+                opengraph: "opengraph.css",
+            },
+            "/assets/",
+            "logo.png",
+            "Website",
+            "Tagline",
+            "Author",
+            None,
+            "3d3c5fd8-6f0e-4bd6-9f5e-5e3a0a6b4c1d",
+            #[cfg(feature = "opengraph")]
+            "http://localhost:4444",
+        )
+        .unwrap()
+    }
+
+    /// Directory unique to the test run, removed at the end of the test
+    struct TempDirectory(PathBuf);
+
+    impl TempDirectory {
+        fn new(name: &str) -> Self {
+            let directory =
+                std::env::temp_dir().join(format!("leptos_ssg-{name}-{}", std::process::id()));
+            std::fs::create_dir_all(&directory).unwrap();
+            Self(directory)
+        }
+    }
+
+    impl Drop for TempDirectory {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn content(markdown: &str) -> Content {
+        let events = Content::markdown_events(markdown);
+        Content {
+            path: PathBuf::from("2025-01-01-hostile.md"),
+            raw: markdown.to_string(),
+            meta: MetadataList::from_markdown(&events).unwrap(),
+            slug: "hostile".into(),
+            assets: None,
+            previous: None,
+            next: None,
+            wikilinks: vec![],
+            rendered_diagrams: Default::default(),
+        }
+    }
+
+    fn render(markdown: &str) -> String {
+        let html = content(markdown).generate_html(&config()).unwrap();
+        assert!(!html.contains("<script"), "unescaped tag in `{html}`");
+        assert!(
+            !html.contains("\" onerror"),
+            "unescaped attribute in `{html}`"
+        );
+        html
+    }
+
+    #[test]
+    fn escape_paragraph() {
+        assert!(render(HOSTILE_TEXT).contains(ESCAPED_TEXT));
+        assert!(render("AT&T \"quoted\"").contains("AT&amp;T &quot;quoted&quot;"));
+    }
+
+    #[test]
+    fn escape_heading() {
+        for level in 1..=6 {
+            let markdown = format!("{} {HOSTILE_TEXT}", "#".repeat(level));
+            assert!(render(&markdown).contains(ESCAPED_TEXT));
+        }
+    }
+
+    #[test]
+    fn escape_inline_formatting() {
+        for delimiter in ["*", "**", "~~", "^", "~"] {
+            let markdown = format!("a {delimiter}{HOSTILE_TEXT}{delimiter} b");
+            assert!(render(&markdown).contains(ESCAPED_TEXT), "{delimiter}");
+        }
+    }
+
+    #[test]
+    fn escape_inline_code() {
+        let html = render("`<script>alert(1)</script>`");
+        assert!(html.contains(ESCAPED_TEXT));
+    }
+
+    #[test]
+    fn escape_code_block() {
+        let html = render(&format!(
+            "```{HOSTILE_ATTRIBUTE}\n<script>alert(1)</script>\n```"
+        ));
+        assert!(html.contains(&format!("language-{ESCAPED_ATTRIBUTE}")));
+        assert!(html.contains("&lt;"));
+
+        let html = render("    <script>alert(1)</script>");
+        assert!(html.contains("&lt;"));
+    }
+
+    #[test]
+    fn code_block_attributes() {
+        let html = render(&format!(
+            "```rust title=\"{HOSTILE_ATTRIBUTE}\" {{2}} linenos\n/* a\nb */\nlet c;\n```"
+        ));
+        assert!(html.contains(
+            "<figcaption class=\"font-mono text-sm px-4 py-1 bg-gray-400 dark:bg-gray-600\">x<"
+        ));
+        assert!(html.contains("<code class=\"language-rust\">"));
+        assert_eq!(html.matches("data-line-number").count(), 3);
+        assert_eq!(html.matches("data-highlighted").count(), 1);
+        // Comments spanning lines are closed and opened again on each line
+        assert_eq!(
+            html.matches("<span").count(),
+            html.matches("</span>").count()
+        );
+    }
+
+    #[test]
+    fn include_file() {
+        let directory = TempDirectory::new("include-file");
+        let directory = &directory.0;
+        std::fs::create_dir_all(directory.join("src")).unwrap();
+        std::fs::write(
+            directory.join("src/main.rs"),
+            "use std::io;\n\n// ANCHOR: main\nfn main() {\n    // ANCHOR: print\n    println!(\"hi\");\n    // ANCHOR_END: print\n}\n// ANCHOR_END: main\n",
+        )
+        .unwrap();
+        let include = |info: &str| {
+            let mut content = content(&format!("```rust {info}\n```"));
+            content.path = directory.join("index.md");
+            content.assets = Some(PathBuf::from("include"));
+            content.generate_html(&config())
+        };
+
+        let html = include(r#"file="src/main.rs" region=main"#).unwrap();
+        assert!(html.contains("main"));
+        assert!(html.contains("println!"));
+        assert!(!html.contains("ANCHOR"));
+        assert!(!html.contains("std"));
+
+        let html = include(r#"file="src/main.rs" lines=1"#).unwrap();
+        assert!(html.contains("std"));
+        assert!(!html.contains("println"));
+
+        for (info, expected) in [
+            (r#"file="missing.rs""#, "failed to read file"),
+            (
+                r#"file="src/main.rs" region=missing"#,
+                "region `missing` not found",
+            ),
+            (r#"file="src/main.rs" lines=5-50"#, "outside of the file"),
+        ] {
+            match include(info) {
+                Err(GenerateHtmlError::Include(_, _, e)) => {
+                    assert!(e.to_string().contains(expected), "{e}")
                 }
+                other => panic!("expected include error for `{info}`, got {other:?}"),
+            }
+        }
+    }
 
-                // ignored events
-                (_, true) => {} // noop
+    #[test]
+    fn rust_check() {
+        let markdown = "```rust\n# let hidden = 1;\nassert_eq!(hidden, 1);\n```\n\n```rust,should_panic\npanic!();\n```\n\n```rust,no_run\nloop {}\n```\n\n```rust,compile_fail\nlet a: u8 = \"a\";\n```\n\n```rust,ignore\nnot rust\n```";
+        let checked = content(markdown);
+        checked.check_rust_code_blocks().unwrap();
+
+        let config = config().with_rust_check(RustCheck {
+            playground_link: true,
+        });
+        let html = checked.generate_html(&config).unwrap();
+        assert!(!html.contains("hidden = 1"));
+        assert_eq!(html.matches("data-playground").count(), 3);
+
+        let failing = content(&format!(
+            "{markdown}\n\nText\n\n```rust\nlet a: u8 = 256;\n```"
+        ));
+        match failing.check_rust_code_blocks() {
+            Err(GenerateHtmlError::RustCheck(_, line, e)) => {
+                assert_eq!(line, 24);
+                assert!(e.starts_with("failed to compile"), "{e}");
+            }
+            other => panic!("expected rust check error, got {other:?}"),
+        }
+
+        // Fences indented in a list item start after the indentation
+        let indented = content("- Item\n\n  ```rust\n  let a: u8 = 256;\n  ```");
+        match indented.check_rust_code_blocks() {
+            Err(GenerateHtmlError::RustCheck(_, line, _)) => assert_eq!(line, 3),
+            other => panic!("expected rust check error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn word_count() {
+        let counted = content(
+            "+++\ntitle = \"Not counted\"\n+++\n\nOne *two* three.\n\n```rust\nlet not = counted;\n```\n\n- four\n- five",
+        );
+        assert_eq!(counted.word_count(), 5);
+        assert_eq!(counted.reading_time(), 1);
+
+        let long = content(&"word ".repeat(WORDS_PER_MINUTE * 2 + 1));
+        assert_eq!(long.reading_time(), 3);
+    }
+
+    #[test]
+    fn excerpt() {
+        let markdown = "+++\ntitle = \"Title\"\n+++\n\nFirst *paragraph*\nwith `code`.\n\nSecond.\n\n<!-- more -->\n\nRest.";
+        let with_marker = content(markdown);
+        assert_eq!(
+            with_marker.excerpt(&config()).as_deref(),
+            Some("First paragraph with code. Second.")
+        );
+        assert_eq!(
+            with_marker.description(&config()),
+            with_marker.excerpt(&config())
+        );
+
+        let config = config().with_raw_html(RawHtml::Reject);
+        let html = with_marker.generate_html(&config).unwrap();
+        assert!(!html.contains("more"));
+
+        let inline = content("Inline <!-- more --> marker");
+        assert_eq!(inline.excerpt(&config).as_deref(), Some("Inline"));
+        assert_eq!(content("No marker").excerpt(&config), None);
+    }
+
+    #[test]
+    fn typography() {
+        let typographic_config = config().with_typography(Typography {
+            smart_punctuation: true,
+            non_breaking_spaces: Some(NonBreakingSpaces::French),
+        });
+        let typographic = content(
+            "+++\ntitle = \"L'été -- « chaud » ?\"\n+++\n\n\"Bonjour\" -- ça va ? Oui : *très* !\n\n```\na -- b ?\n```",
+        );
+
+        let html = typographic.generate_html(&typographic_config).unwrap();
+        assert!(html.contains("\u{201c}Bonjour\u{201d} \u{2013} ça va\u{202f}? Oui\u{a0}: "));
+        assert!(html.contains("\u{202f}!"));
+        assert!(html.contains("a -- b ?"));
+
+        let feed = typographic.raw_html(&typographic_config).unwrap();
+        assert!(feed.contains("ça va\u{202f}?"));
+
+        assert_eq!(
+            typographic.title(&typographic_config),
+            "L\u{2019}été \u{2013} «\u{202f}chaud\u{202f}»\u{202f}?"
+        );
+        assert_eq!(typographic.title(&config()), "L'été -- « chaud » ?");
+
+        let with_excerpt = content("\"Bonjour\" -- ça va ?\n\n<!-- more -->\n\nRest");
+        assert_eq!(
+            with_excerpt.description(&typographic_config).as_deref(),
+            Some("\u{201c}Bonjour\u{201d} \u{2013} ça va\u{202f}?")
+        );
+        let with_description = content("+++\ndescription = \"Oui : *très* !\"\n+++\n\nText");
+        assert_eq!(
+            with_description.description(&typographic_config).as_deref(),
+            Some("Oui\u{a0}: *très*\u{202f}!")
+        );
+    }
+
+    #[test]
+    fn wikilinks() {
+        let mut list = vec![
+            content(
+                "+++\ntitle = \"First post\"\n+++\n\nSee [[Second Post]] and [[second|the second]].",
+            ),
+            content("+++\ntitle = \"Second post\"\n+++\n\nBack to [[Unknown]]."),
+        ];
+        list[1].slug = "second".into();
+        Content::resolve_wikilinks(&mut list);
+
+        let html = list[0].generate_html(&config()).unwrap();
+        assert!(html.contains("<a href=\"/second\""));
+        assert!(html.contains(">the second</a>"));
+        let feed = list[0].raw_html(&config()).unwrap();
+        assert!(feed.contains("<a href=\"https://example.com/second/\">Second Post</a>"));
+
+        match list[1].generate_html(&config()) {
+            Err(GenerateHtmlError::UnresolvedWikilink(_, target)) => assert_eq!(target, "Unknown"),
+            other => panic!("expected unresolved wikilink, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn image_grid() {
+        let directory = TempDirectory::new("image-grid");
+        let directory = &directory.0;
+        std::fs::create_dir_all(directory.join("images")).unwrap();
+        for file in ["a.svg", "b.svg", "notes.txt"] {
+            std::fs::write(directory.join("images").join(file), "").unwrap();
+        }
+        std::fs::write(
+            directory.join("images/captions.toml"),
+            "[\"b.svg\"]\nalt = \"The letter B\"\ncaption = \"Second letter\"\n",
+        )
+        .unwrap();
+        let grid = |attributes: &str| {
+            let mut content = content(&format!("<ImageGrid src=\"images/\" {attributes} />"));
+            content.path = directory.join("index.md");
+            content.assets = Some(PathBuf::from("image-grid"));
+            content.generate_html(&config())
+        };
+
+        let html = grid("columns=\"3\"").unwrap();
+        assert!(html.contains("grid-cols-3"));
+        assert!(!html.contains("notes.txt"));
+        assert!(!html.contains("captions.toml"));
+        assert!(html.find("images/a.svg").unwrap() < html.find("images/b.svg").unwrap());
+        assert!(html.contains("alt=\"a.svg\""));
+        assert!(html.contains("alt=\"The letter B\""));
+        assert!(html.contains("Second letter</figcaption>"));
+
+        let html = grid("reverse").unwrap();
+        assert!(html.find("images/b.svg").unwrap() < html.find("images/a.svg").unwrap());
+
+        let html = grid("extensions=\"txt\"").unwrap();
+        assert!(html.contains("notes.txt"));
+        assert!(!html.contains("a.svg"));
+
+        assert!(grid("columns=\"9\"").is_err());
+        assert!(grid("sort=\"size\"").is_err());
+    }
+
+    #[test]
+    fn external_links() {
+        let markdown = "[Internal](/about) [Same host](https://example.com/about) [External](https://www.rust-lang.org)";
+
+        let html = content(markdown).generate_html(&config()).unwrap();
+        assert!(html.contains("<a href=\"/about\" class="));
+        assert!(html.contains("<a href=\"https://example.com/about\" class="));
+        assert!(
+            html.contains(
+                "<a href=\"https://www.rust-lang.org\" rel=\"noopener noreferrer\" class="
+            )
+        );
+        assert!(!html.contains("data-external-link"));
+
+        let policy_config = config().with_external_links(ExternalLinks {
+            nofollow: true,
+            icon: true,
+            target: Some("_blank"),
+        });
+        let html = content(markdown).generate_html(&policy_config).unwrap();
+        assert!(html.contains("<a href=\"https://example.com/about\" class="));
+        assert!(html.contains(
+            "<a href=\"https://www.rust-lang.org\" rel=\"noopener noreferrer nofollow\" target=\"_blank\" class="
+        ));
+        assert_eq!(html.matches("data-external-link").count(), 1);
+
+        // Links written by components follow the same policy
+        let embed = "<Embed url=\"https://www.rust-lang.org\" title=\"Rust\" />\n\n<Embed url=\"https://example.com/about\" title=\"About\" />";
+        let html = content(embed).generate_html(&policy_config).unwrap();
+        assert!(html.contains(
+            "href=\"https://www.rust-lang.org\" rel=\"noopener noreferrer nofollow\" target=\"_blank\""
+        ));
+        assert!(html.contains("href=\"https://example.com/about\" class="));
+        assert_eq!(html.matches("data-external-link").count(), 1);
+    }
 
-                // unhandled events
-                (event, false) => {
-                    return Err(GenerateHtmlError::UnknownMarkdownEvent(event.into_static()));
+    #[test]
+    fn escape_link() {
+        let html = render(&format!("[{HOSTILE_TEXT}](<{HOSTILE_ATTRIBUTE}>)"));
+        assert!(html.contains(ESCAPED_TEXT));
+        assert!(html.contains(&format!("href=\"{ESCAPED_ATTRIBUTE}\"")));
+    }
+
+    #[test]
+    fn escape_image() {
+        let markdown = format!("![{HOSTILE_TEXT}](<{HOSTILE_ATTRIBUTE}> \"{HOSTILE_TEXT}\")");
+
+        // Figure, when the image is alone in its paragraph
+        let html = render(&markdown);
+        assert!(html.contains(&format!("src=\"{ESCAPED_ATTRIBUTE}\"")));
+        assert!(html.contains(&format!("alt=\"{ESCAPED_TEXT}\"")));
+        assert!(html.contains(&format!("{ESCAPED_TEXT}</figcaption>")));
+
+        // Image with a title, among text
+        let html = render(&format!("Text {markdown}"));
+        assert!(html.contains(&format!("title=\"{ESCAPED_TEXT}\"")));
+    }
+
+    #[test]
+    fn escape_lists() {
+        assert!(render(&format!("- {HOSTILE_TEXT}")).contains(ESCAPED_TEXT));
+        assert!(render(&format!("1. {HOSTILE_TEXT}")).contains(ESCAPED_TEXT));
+        assert!(render(&format!("- [x] {HOSTILE_TEXT}")).contains(ESCAPED_TEXT));
+    }
+
+    #[test]
+    fn escape_definition_list() {
+        let html = render(&format!("{HOSTILE_TEXT}\n: {HOSTILE_TEXT}"));
+        assert_eq!(html.matches(ESCAPED_TEXT).count(), 2);
+    }
+
+    #[test]
+    fn escape_table() {
+        let html = render(&format!("| {HOSTILE_TEXT} |\n| --- |\n| {HOSTILE_TEXT} |"));
+        assert_eq!(html.matches(ESCAPED_TEXT).count(), 2);
+    }
+
+    #[test]
+    fn escape_quotes() {
+        assert!(render(&format!("> {HOSTILE_TEXT}")).contains(ESCAPED_TEXT));
+        assert!(render(&format!("> [!WARNING]\n> {HOSTILE_TEXT}")).contains(ESCAPED_TEXT));
+    }
+
+    #[test]
+    fn escape_footnotes() {
+        let html = render(&format!(
+            "Text[^{HOSTILE_ATTRIBUTE}]\n\n[^{HOSTILE_ATTRIBUTE}]: {HOSTILE_TEXT}"
+        ));
+        assert!(html.contains(ESCAPED_TEXT));
+    }
+
+    #[test]
+    fn escape_math() {
+        let config = config().with_math();
+        for markdown in [
+            r#"$a < b \text{"><script>}$"#,
+            r#"$$a < b \text{"><script>}$$"#,
+        ] {
+            let html = content(markdown).generate_html(&config).unwrap();
+            assert!(html.contains("<math"));
+            assert!(html.contains("&lt;"));
+            assert!(!html.contains("<script"));
+        }
+    }
+
+    #[test]
+    fn currency() {
+        let markdown = "Range $5-$10 or $20/$30";
+        let html = render(markdown);
+        assert!(html.contains("Range $5-$10 or $20/$30"), "{html}");
+        assert!(!html.contains("<math"), "{html}");
+        let feed = content(markdown).raw_html(&config()).unwrap();
+        assert!(feed.contains("Range $5-$10 or $20/$30"), "{feed}");
+    }
+
+    #[test]
+    fn escape_line_breaks() {
+        let html = render(&format!("{HOSTILE_TEXT}  \n{HOSTILE_TEXT}\n{HOSTILE_TEXT}"));
+        assert_eq!(html.matches(ESCAPED_TEXT).count(), 3);
+    }
+
+    #[test]
+    fn drop_html() {
+        render("<script>alert(1)</script>");
+        render(&format!("<ImageGrid src=\"{HOSTILE_ATTRIBUTE}\" />"));
+
+        let markdown = "<div>block</div>\n\nPress <kbd>Ctrl</kbd>";
+        let html = render(markdown);
+        assert!(!html.contains("<div>") && !html.contains("<kbd>"), "{html}");
+        let feed = content(markdown).raw_html(&config()).unwrap();
+        assert!(!feed.contains("<div>") && !feed.contains("<kbd>"), "{feed}");
+    }
+
+    #[test]
+    fn sanitize_html() {
+        let config = config().with_raw_html(RawHtml::Sanitize);
+        let render = |markdown| content(markdown).generate_html(&config).unwrap();
+        let html = render("Press <kbd>Ctrl</kbd> <span onclick=\"alert(1)\">now</span>");
+        assert!(html.contains("<kbd>Ctrl</kbd>"));
+        assert!(html.contains("<span>now</span>"));
+        assert!(!html.contains("onclick"));
+
+        let html = render(
+            "<div onclick=\"alert(1)\">\n<abbr title=\"HyperText\">HTML</abbr>\n<script>alert(1)</script>\n</div>",
+        );
+        assert!(html.contains("<abbr title=\"HyperText\">HTML</abbr>"));
+        assert!(!html.contains("onclick"));
+        assert!(!html.contains("alert"));
+
+        // Feeds, including children of components
+        let config = config.with_components(COMPONENTS);
+        for markdown in [
+            "Press <span onclick=\"alert(1)\">x</span>",
+            "<div onclick=\"alert(1)\">x</div>",
+            "<Details summary=\"Title\">Press <span onclick=\"alert(1)\">x</span></Details>",
+        ] {
+            let feed = content(markdown).raw_html(&config).unwrap();
+            assert!(feed.contains(">x</"), "{feed}");
+            assert!(!feed.contains("onclick"), "{feed}");
+        }
+    }
+
+    #[test]
+    fn passthrough_html() {
+        let config = config().with_raw_html(RawHtml::Passthrough);
+        let markdown = "<div id=\"block\">\n<video src=\"a.mp4\"></video>\n</div>\n\nPress <kbd id=\"key\">Ctrl</kbd>";
+        let html = content(markdown).generate_html(&config).unwrap();
+        assert!(html.contains("<div id=\"block\">\n<video src=\"a.mp4\"></video>\n</div>"));
+        assert!(html.contains("<kbd id=\"key\">Ctrl</kbd>"));
+    }
+
+    #[test]
+    fn diagrams() {
+        let markdown = "```dot\ndigraph { a -> b }\n```\n\n```mermaid\ngraph TD; a-->b\n```";
+        let diagrams_content = content(markdown);
+        let html = diagrams_content.generate_html(&config()).unwrap();
+        assert!(html.contains("<div class=\"overflow-x-auto bg-white p-4 my-4\" role=\"img\" aria-label=\"Diagram\" data-diagram=\"dot\"><svg"));
+        assert!(!html.contains("<?xml"));
+        assert!(html.contains("<code class=\"language-mermaid\">"));
+
+        let diagrams = diagrams_content.diagrams().unwrap();
+        assert_eq!(diagrams.len(), 1);
+        assert_eq!(diagrams[0].0, "diagram-1.svg");
+        // Rendered once, for the page and its file
+        assert!(html.contains(diagram::inline(&diagrams[0].1)));
+        assert_eq!(diagrams_content.rendered_diagrams.lock().unwrap().len(), 1);
+
+        let feed = diagrams_content.raw_html(&config()).unwrap();
+        assert!(
+            feed.contains(
+                "<img src=\"https://example.com/hostile/diagram-1.svg\" alt=\"Diagram\">"
+            )
+        );
+        assert!(!feed.contains("a -&gt; b"));
+
+        match content("```dot\ndigraph {\n```").generate_html(&config()) {
+            Err(GenerateHtmlError::Diagram(..)) => {}
+            other => panic!("expected diagram error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reject_html() {
+        let config = config().with_raw_html(RawHtml::Reject);
+        for markdown in ["<div>block</div>", "Press <kbd>Ctrl</kbd>"] {
+            match content(markdown).generate_html(&config) {
+                Err(GenerateHtmlError::RawHtml(path, snippet)) => {
+                    assert_eq!(path, PathBuf::from("2025-01-01-hostile.md"));
+                    assert!(markdown.contains(&snippet));
                 }
+                result => panic!("raw HTML was not rejected: {result:?}"),
+            }
+            match content(markdown).raw_html(&config) {
+                Err(GenerateHtmlError::RawHtml(_, snippet)) => assert!(markdown.contains(&snippet)),
+                result => panic!("raw HTML was not rejected in the feed: {result:?}"),
             }
         }
+    }
 
-        // Do not add any whitespace between views, they can be interpreted as a space in web
-        // browsers, causing unwanted visual effects.
-        let html = views.join("");
+    fn details(
+        attributes: &Attributes,
+        children: Option<&str>,
+        _content: &Content,
+        _config: &BuildConfig,
+    ) -> Result<leptos::prelude::AnyView, ComponentError> {
+        use leptos::prelude::*;
+
+        let summary = attributes.required("summary")?.to_string();
+        let level = attributes.parse::<u8>("level")?.unwrap_or(1);
+        let open = attributes.contains("open");
+        let children = children.unwrap_or_default().to_string();
+        Ok(view! {
+            <details open=open data-level=level><summary>{summary}</summary><div inner_html=children></div></details>
+        }
+        .into_any())
+    }
 
-        Ok(html)
+    const COMPONENTS: &[Component] = &[Component {
+        tag: "Details",
+        render: details,
+        feed: None,
+    }];
+
+    fn render_components(markdown: &str) -> Result<String, GenerateHtmlError> {
+        content(markdown).generate_html(&config().with_components(COMPONENTS))
+    }
+
+    #[test]
+    fn component_attributes() {
+        let html = render_components("<Details summary=\"Title\" level=\"2\" open />").unwrap();
+        assert!(html.contains("<details open data-level=\"2\"><summary>Title</summary>"));
+
+        let html = render_components("<Details summary=\"Title\"></Details>").unwrap();
+        assert!(html.contains("<details data-level=\"1\"><summary>Title</summary>"));
+    }
+
+    #[test]
+    fn component_children() {
+        // Children in the same HTML block as the component
+        let html =
+            render_components("<Details summary=\"Title\">\n*markdown*\n</Details>").unwrap();
+        assert!(html.contains("</summary><div><p"));
+
+        // Children starting in the block of the opening tag, ending in following blocks
+        let markdown = "<Details summary=\"Title\">\nHidden *text*\n\nMore\n\n</Details>";
+        let html = render_components(markdown).unwrap();
+        assert!(html.contains("</summary><div>"));
+        assert!(html.contains("Hidden <em"));
+        assert!(html.contains("More</p>"));
+        let feed = content(markdown)
+            .raw_html(&config().with_components(COMPONENTS))
+            .unwrap();
+        assert!(feed.contains("Hidden <em>text</em>"));
+        assert!(feed.contains("<p>More</p>"));
+
+        // Children in blocks between the tags of the component
+        let html = render_components(
+            "<Details summary=\"Outer\">\n\n*markdown*\n\n<Details summary=\"Inner\">\n\n**nested**\n\n</Details>\n\n</Details>\n\nAfter",
+        )
+        .unwrap();
+        assert!(html.starts_with("<details data-level=\"1\"><summary>Outer</summary>"));
+        assert!(html.contains("<em"));
+        assert!(html.contains("<summary>Inner</summary>"));
+        assert!(html.contains("After</p>"));
+    }
+
+    #[test]
+    fn component_errors() {
+        match render_components("<Details open />") {
+            Err(GenerateHtmlError::Component(_, tag, ComponentError::MissingAttribute(name))) => {
+                assert_eq!(tag, "Details");
+                assert_eq!(name, "summary");
+            }
+            result => panic!("missing attribute was accepted: {result:?}"),
+        }
+
+        match render_components("<Details summary=\"Title\" level=\"high\" />") {
+            Err(GenerateHtmlError::Component(_, _, ComponentError::InvalidAttribute(name, ..))) => {
+                assert_eq!(name, "level");
+            }
+            result => panic!("invalid attribute was accepted: {result:?}"),
+        }
+
+        match render_components("<Details summary=\"Title\">\n\nNever closed") {
+            Err(GenerateHtmlError::UnclosedComponent(_, tag)) => assert_eq!(tag, "Details"),
+            result => panic!("unclosed component was accepted: {result:?}"),
+        }
     }
 }
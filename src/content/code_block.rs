@@ -0,0 +1,237 @@
+//! Fenced code blocks, with attributes written after their language:
+//! `rust title="main.rs" {3-5} linenos`
+
+use super::escape_html;
+use super::syntax_highlight;
+use std::ops::RangeInclusive;
+use tailwind_fuse::tw_join;
+
+/// Attributes of a fenced code block, parsed from its info string
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct CodeBlockInfo {
+    pub language: Option<String>,
+    /// File name shown above the code
+    pub title: Option<String>,
+    pub line_numbers: bool,
+    /// Lines to highlight, starting at 1
+    pub highlighted_lines: Vec<RangeInclusive<usize>>,
+    /// Other flags and values, such as `no_run` in `rust,no_run`
+    pub attributes: Vec<(String, Option<String>)>,
+}
+
+impl CodeBlockInfo {
+    pub fn parse(info: &str) -> Self {
+        let mut code_block_info = Self::default();
+
+        for (idx, token) in tokens(info).into_iter().enumerate() {
+            if let Some(ranges) = token.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+                code_block_info
+                    .highlighted_lines
+                    .extend(parse_ranges(ranges));
+            } else if let Some((key, value)) = token.split_once('=')
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                let value = value.trim_matches('"').to_string();
+                match key {
+                    "title" => code_block_info.title = Some(value),
+                    _ => code_block_info
+                        .attributes
+                        .push((key.to_string(), Some(value))),
+                }
+            } else {
+                // Flags can follow the language, separated by commas like rustdoc
+                for (flag_idx, flag) in token.split(',').filter(|f| !f.is_empty()).enumerate() {
+                    match (idx, flag_idx, flag) {
+                        (0, 0, language) => code_block_info.language = Some(language.to_string()),
+                        (_, _, "linenos") => code_block_info.line_numbers = true,
+                        (_, _, flag) => code_block_info.attributes.push((flag.to_string(), None)),
+                    }
+                }
+            }
+        }
+
+        code_block_info
+    }
+
+    /// Whether a flag, such as `no_run`, is present
+    pub fn flag(&self, name: &str) -> bool {
+        self.attributes
+            .iter()
+            .any(|(key, value)| key == name && value.is_none())
+    }
+
+    /// Value of an attribute, such as `file` in `file="main.rs"`
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    fn is_highlighted(&self, line: usize) -> bool {
+        self.highlighted_lines
+            .iter()
+            .any(|range| range.contains(&line))
+    }
+}
+
+/// Split an info string on whitespace, keeping quoted values together
+fn tokens(info: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut quoted = false;
+    for c in info.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                token.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            c => token.push(c),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Parse ranges of lines like `3-5,7`
+fn parse_ranges(ranges: &str) -> Vec<RangeInclusive<usize>> {
+    ranges
+        .split(',')
+        .map(str::trim)
+        .filter(|range| !range.is_empty())
+        .filter_map(|range| {
+            let parsed = match range.split_once('-') {
+                Some((start, end)) => start
+                    .trim()
+                    .parse()
+                    .and_then(|start| end.trim().parse().map(|end| start..=end)),
+                None => range.parse().map(|line| line..=line),
+            };
+            parsed
+                .inspect_err(|_| {
+                    println!("Code block: invalid range of lines `{range}`, ignoring it")
+                })
+                .ok()
+        })
+        .collect()
+}
+
+/// Render a highlighted code block, with its title, line numbers and highlighted lines, and a
+/// link to run it
+pub(crate) fn render(
+    info: &CodeBlockInfo,
+    code: &str,
+    playground_url: Option<&str>,
+) -> Result<String, syntect::Error> {
+    let lines = syntax_highlight::highlight_lines(code, info.language.as_deref())?;
+
+    let mut highlighted = String::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let number = idx + 1;
+        match (info.line_numbers, info.is_highlighted(number)) {
+            (false, false) => highlighted.push_str(line),
+            (line_numbers, is_highlighted) => {
+                let class = match is_highlighted {
+                    true => format!(
+                        " class=\"{}\" data-highlighted",
+                        tw_join!(
+                            "inline-block",
+                            "min-w-full",
+                            "bg-yellow-200",
+                            "dark:bg-yellow-900"
+                        )
+                    ),
+                    false => String::new(),
+                };
+                let line_number = match line_numbers {
+                    true => format!(
+                        "<span class=\"{}\" data-line-number aria-hidden=\"true\">{number}</span>",
+                        tw_join!(
+                            "inline-block",
+                            "w-8",
+                            "pr-4",
+                            "text-right",
+                            "select-none",
+                            "opacity-50"
+                        )
+                    ),
+                    false => String::new(),
+                };
+                highlighted.push_str(&format!("<span{class}>{line_number}{line}</span>"));
+            }
+        }
+        highlighted.push('\n');
+    }
+
+    let class_code_language = info
+        .language
+        .as_ref()
+        .map(|language| format!(" class=\"language-{}\"", escape_html(language)))
+        .unwrap_or_default();
+    let mut pre = format!(
+        "<pre class=\"{}\"><code{}>{highlighted}</code></pre>",
+        tw_join!(
+            "overflow-x-auto",
+            "font-mono",
+            "p-4",
+            syntax_highlight::CODE_CLASS
+        ),
+        class_code_language
+    );
+    if let Some(playground_url) = playground_url {
+        pre.push_str(&format!(
+            "<p class=\"{}\"><a href=\"{}\" class=\"{}\" data-playground>Run in the Rust playground</a></p>",
+            tw_join!("text-sm", "text-right"),
+            escape_html(playground_url),
+            tw_join!("underline", "text-sky-900", "dark:text-yellow-400")
+        ));
+    }
+
+    Ok(match &info.title {
+        Some(title) => format!(
+            "<figure data-code-title><figcaption class=\"{}\">{}</figcaption>{pre}</figure>",
+            tw_join!(
+                "font-mono",
+                "text-sm",
+                "px-4",
+                "py-1",
+                "bg-gray-400",
+                "dark:bg-gray-600"
+            ),
+            escape_html(title)
+        ),
+        None => pre,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let info = CodeBlockInfo::parse(r#"rust,no_run title="src/main.rs" {1,3-4} linenos"#);
+        assert_eq!(info.language.as_deref(), Some("rust"));
+        assert_eq!(info.title.as_deref(), Some("src/main.rs"));
+        assert!(info.line_numbers);
+        assert_eq!(info.highlighted_lines, vec![1..=1, 3..=4]);
+        assert_eq!(info.attributes, vec![("no_run".to_string(), None)]);
+        assert!(info.flag("no_run"));
+
+        let info = CodeBlockInfo::parse(r#"toml file="Cargo.toml" title="with spaces.toml""#);
+        assert_eq!(info.language.as_deref(), Some("toml"));
+        assert_eq!(info.title.as_deref(), Some("with spaces.toml"));
+        assert_eq!(info.get("file"), Some("Cargo.toml"));
+
+        assert_eq!(CodeBlockInfo::parse(""), CodeBlockInfo::default());
+    }
+}
@@ -0,0 +1,58 @@
+use layout::backends::svg::SVGWriter;
+use layout::gv::{DotParser, GraphBuilder};
+
+/// Code blocks drawn as diagrams, depending on their language
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Diagram {
+    Dot,
+    Mermaid,
+    Pikchr,
+}
+
+impl Diagram {
+    pub fn from_language(language: &str) -> Option<Self> {
+        match language {
+            "dot" | "graphviz" => Some(Self::Dot),
+            "mermaid" => Some(Self::Mermaid),
+            "pikchr" => Some(Self::Pikchr),
+            _ => None,
+        }
+    }
+
+    /// Diagrams without a renderer written in Rust are shown as code
+    pub fn has_renderer(&self) -> bool {
+        match self {
+            Self::Dot => true,
+            Self::Mermaid | Self::Pikchr => false,
+        }
+    }
+
+    /// Render a diagram to a SVG document
+    pub fn render(&self, source: &str) -> Result<String, String> {
+        match self {
+            Self::Dot => {
+                let graph = DotParser::new(source).process()?;
+                let mut builder = GraphBuilder::new();
+                builder.visit_graph(&graph);
+
+                let mut svg = SVGWriter::new();
+                builder.get().do_it(false, false, false, &mut svg);
+                Ok(svg.finalize())
+            }
+            Self::Mermaid | Self::Pikchr => Err(format!("no renderer available for {self:?}")),
+        }
+    }
+}
+
+/// Name of the file of a diagram, numbered in order of appearance in the content
+pub(crate) fn file_name(number: usize) -> String {
+    format!("diagram-{number}.svg")
+}
+
+/// SVG document without its XML declaration, to be inserted in a HTML page
+pub(crate) fn inline(svg: &str) -> &str {
+    match svg.find("<svg") {
+        Some(idx) => &svg[idx..],
+        None => svg,
+    }
+}
@@ -0,0 +1,81 @@
+//! Code blocks showing a file from the assets of the content, instead of their own code:
+//! `rust file="src/main.rs" lines=3-10` or `rust file="src/main.rs" region=setup`.
+//!
+//! Regions are delimited by `ANCHOR: name` and `ANCHOR_END: name` markers in comments, like
+//! mdBook. Lines with markers are not included.
+
+use std::path::Path;
+
+#[derive(Debug, thiserror::Error)]
+pub enum IncludeError {
+    #[error("content has no assets directory to include files from")]
+    NoAssets,
+    #[error("failed to read file: {0}")]
+    ReadFile(std::io::ErrorKind),
+    #[error("invalid range of lines `{0}`")]
+    InvalidLines(String),
+    #[error("lines `{0}` are outside of the file, it has {1} lines")]
+    LinesOutOfFile(String, usize),
+    #[error("region `{0}` not found")]
+    MissingRegion(String),
+}
+
+const ANCHOR: &str = "ANCHOR:";
+const ANCHOR_END: &str = "ANCHOR_END:";
+
+/// Read a file located in a directory, keeping a range of lines as numbered in the file, then a
+/// region
+pub(crate) fn read(
+    directory: Option<&Path>,
+    file: &str,
+    region: Option<&str>,
+    lines: Option<&str>,
+) -> Result<String, IncludeError> {
+    let directory = directory.ok_or(IncludeError::NoAssets)?;
+    let source = std::fs::read_to_string(directory.join(file))
+        .map_err(|e| IncludeError::ReadFile(e.kind()))?;
+    let mut included: Vec<&str> = source.lines().collect();
+
+    if let Some(lines) = lines {
+        let (start, end) = match lines.split_once('-') {
+            Some((start, "")) => (start.trim().parse(), Ok(included.len())),
+            Some((start, end)) => (start.trim().parse(), end.trim().parse()),
+            None => (lines.trim().parse(), lines.trim().parse()),
+        };
+        let (start, end): (usize, usize) = match (start, end) {
+            (Ok(start), Ok(end)) if start >= 1 && start <= end => (start, end),
+            _ => return Err(IncludeError::InvalidLines(lines.to_string())),
+        };
+        if end > included.len() {
+            return Err(IncludeError::LinesOutOfFile(
+                lines.to_string(),
+                included.len(),
+            ));
+        }
+        included = included[start - 1..end].to_vec();
+    }
+
+    if let Some(region) = region {
+        let is_marker = |line: &str, marker: &str| {
+            line.split_once(marker)
+                .is_some_and(|(_, name)| name.split_whitespace().next() == Some(region))
+        };
+        let start = included
+            .iter()
+            .position(|line| is_marker(line, ANCHOR))
+            .ok_or(IncludeError::MissingRegion(region.to_string()))?;
+        let end = included[start..]
+            .iter()
+            .position(|line| is_marker(line, ANCHOR_END))
+            .map(|end| start + end)
+            .ok_or(IncludeError::MissingRegion(region.to_string()))?;
+        included = included[start + 1..end].to_vec();
+    }
+
+    // Region markers are not part of the code
+    included.retain(|line| !line.contains(ANCHOR) && !line.contains(ANCHOR_END));
+
+    let mut code = included.join("\n");
+    code.push('\n');
+    Ok(code)
+}
@@ -0,0 +1,939 @@
+//! Convert a subset of LaTeX to MathML, to render equations without JavaScript.
+//!
+//! Supported: identifiers, numbers, operators, groups, superscripts and subscripts, fractions,
+//! roots, greek letters, common symbols and functions, font styles, accents, `\left`/`\right`
+//! delimiters, spaces, `\text`, and matrix-like environments. Unknown commands are rendered as
+//! errors in the equation instead of failing the build, like equations nested too deeply.
+
+use super::escape_html as escape;
+use std::iter::Peekable;
+use std::str::Chars;
+
+#[derive(Debug)]
+enum Node {
+    Identifier(String, Option<&'static str>),
+    Number(String),
+    Operator(String),
+    Text(String),
+    Space(&'static str),
+    Row(Vec<Node>),
+    Fraction(Box<Node>, Box<Node>),
+    Root(Box<Node>, Option<Box<Node>>),
+    Scripts {
+        base: Box<Node>,
+        sub: Option<Box<Node>>,
+        sup: Option<Box<Node>>,
+    },
+    Over(Box<Node>, &'static str),
+    Under(Box<Node>, &'static str),
+    /// Large operators, with scripts under and over them in display mode
+    Limits(Box<Node>),
+    Fenced(String, Vec<Node>, String),
+    Table {
+        rows: Vec<Vec<Node>>,
+        align: &'static str,
+        open: &'static str,
+        close: &'static str,
+    },
+    Error(String),
+}
+
+/// Where a row of nodes stopped
+#[derive(Debug, PartialEq)]
+enum RowEnd {
+    Input,
+    Group,
+    Right,
+    Cell,
+    Line,
+    /// `\end{name}`, with the name of the environment
+    Environment(String),
+}
+
+/// Maximum nesting of groups, commands and environments. Deeper equations are not rendered, to
+/// keep recursion of the parser bounded.
+const MAX_DEPTH: usize = 64;
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    /// Nesting of the token being parsed
+    depth: usize,
+    /// Nesting went over `MAX_DEPTH`, the rest of the input was skipped
+    too_deep: bool,
+}
+
+const GREEK: &[(&str, &str)] = &[
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ϵ"),
+    ("varepsilon", "ε"),
+    ("zeta", "ζ"),
+    ("eta", "η"),
+    ("theta", "θ"),
+    ("vartheta", "ϑ"),
+    ("iota", "ι"),
+    ("kappa", "κ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("nu", "ν"),
+    ("xi", "ξ"),
+    ("pi", "π"),
+    ("varpi", "ϖ"),
+    ("rho", "ρ"),
+    ("varrho", "ϱ"),
+    ("sigma", "σ"),
+    ("varsigma", "ς"),
+    ("tau", "τ"),
+    ("upsilon", "υ"),
+    ("phi", "ϕ"),
+    ("varphi", "φ"),
+    ("chi", "χ"),
+    ("psi", "ψ"),
+    ("omega", "ω"),
+    ("Gamma", "Γ"),
+    ("Delta", "Δ"),
+    ("Theta", "Θ"),
+    ("Lambda", "Λ"),
+    ("Xi", "Ξ"),
+    ("Pi", "Π"),
+    ("Sigma", "Σ"),
+    ("Upsilon", "Υ"),
+    ("Phi", "Φ"),
+    ("Psi", "Ψ"),
+    ("Omega", "Ω"),
+];
+
+const SYMBOLS: &[(&str, &str)] = &[
+    ("infty", "∞"),
+    ("partial", "∂"),
+    ("nabla", "∇"),
+    ("hbar", "ℏ"),
+    ("ell", "ℓ"),
+    ("emptyset", "∅"),
+    ("aleph", "ℵ"),
+];
+
+const OPERATORS: &[(&str, &str)] = &[
+    ("pm", "±"),
+    ("mp", "∓"),
+    ("times", "×"),
+    ("div", "÷"),
+    ("cdot", "⋅"),
+    ("ast", "∗"),
+    ("star", "⋆"),
+    ("circ", "∘"),
+    ("bullet", "∙"),
+    ("le", "≤"),
+    ("leq", "≤"),
+    ("ge", "≥"),
+    ("geq", "≥"),
+    ("ne", "≠"),
+    ("neq", "≠"),
+    ("approx", "≈"),
+    ("equiv", "≡"),
+    ("sim", "∼"),
+    ("simeq", "≃"),
+    ("cong", "≅"),
+    ("propto", "∝"),
+    ("ll", "≪"),
+    ("gg", "≫"),
+    ("in", "∈"),
+    ("notin", "∉"),
+    ("ni", "∋"),
+    ("subset", "⊂"),
+    ("subseteq", "⊆"),
+    ("supset", "⊃"),
+    ("supseteq", "⊇"),
+    ("cup", "∪"),
+    ("cap", "∩"),
+    ("setminus", "∖"),
+    ("wedge", "∧"),
+    ("land", "∧"),
+    ("vee", "∨"),
+    ("lor", "∨"),
+    ("neg", "¬"),
+    ("lnot", "¬"),
+    ("forall", "∀"),
+    ("exists", "∃"),
+    ("to", "→"),
+    ("rightarrow", "→"),
+    ("leftarrow", "←"),
+    ("gets", "←"),
+    ("leftrightarrow", "↔"),
+    ("Rightarrow", "⇒"),
+    ("implies", "⇒"),
+    ("Leftarrow", "⇐"),
+    ("Leftrightarrow", "⇔"),
+    ("iff", "⇔"),
+    ("mapsto", "↦"),
+    ("uparrow", "↑"),
+    ("downarrow", "↓"),
+    ("ldots", "…"),
+    ("dots", "…"),
+    ("cdots", "⋯"),
+    ("vdots", "⋮"),
+    ("ddots", "⋱"),
+    ("perp", "⊥"),
+    ("parallel", "∥"),
+    ("mid", "∣"),
+    ("angle", "∠"),
+    ("oplus", "⊕"),
+    ("otimes", "⊗"),
+    ("langle", "⟨"),
+    ("rangle", "⟩"),
+    ("lfloor", "⌊"),
+    ("rfloor", "⌋"),
+    ("lceil", "⌈"),
+    ("rceil", "⌉"),
+    ("vert", "|"),
+    ("Vert", "‖"),
+    ("|", "‖"),
+    ("{", "{"),
+    ("}", "}"),
+    ("lbrace", "{"),
+    ("rbrace", "}"),
+    ("%", "%"),
+    ("$", "$"),
+    ("#", "#"),
+    ("&", "&"),
+    ("_", "_"),
+];
+
+/// Operators with limits above and below them in display mode
+const LARGE_OPERATORS: &[(&str, &str)] = &[
+    ("sum", "∑"),
+    ("prod", "∏"),
+    ("coprod", "∐"),
+    ("bigcup", "⋃"),
+    ("bigcap", "⋂"),
+    ("bigoplus", "⨁"),
+    ("bigotimes", "⨂"),
+];
+
+/// Integrals always have their limits as scripts
+const INTEGRALS: &[(&str, &str)] = &[("int", "∫"), ("iint", "∬"), ("iiint", "∭"), ("oint", "∮")];
+
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh",
+    "log", "ln", "lg", "exp", "det", "dim", "ker", "deg", "gcd", "arg", "Pr", "hom",
+];
+
+/// Functions with limits under them in display mode
+const LIMIT_FUNCTIONS: &[&str] = &["lim", "max", "min", "sup", "inf", "limsup", "liminf"];
+
+const ACCENTS: &[(&str, &str)] = &[
+    ("hat", "^"),
+    ("widehat", "^"),
+    ("bar", "¯"),
+    ("overline", "¯"),
+    ("vec", "→"),
+    ("overrightarrow", "→"),
+    ("dot", "˙"),
+    ("ddot", "¨"),
+    ("tilde", "~"),
+    ("widetilde", "~"),
+    ("check", "ˇ"),
+    ("breve", "˘"),
+];
+
+const SPACES: &[(&str, &str)] = &[
+    (",", "0.1667em"),
+    (":", "0.2222em"),
+    (">", "0.2222em"),
+    (";", "0.2778em"),
+    (" ", "0.25em"),
+    ("quad", "1em"),
+    ("qquad", "2em"),
+    ("!", "-0.1667em"),
+];
+
+const FONTS: &[(&str, &str)] = &[
+    ("mathbb", "double-struck"),
+    ("mathbf", "bold"),
+    ("boldsymbol", "bold-italic"),
+    ("mathit", "italic"),
+    ("mathrm", "normal"),
+    ("operatorname", "normal"),
+    ("mathcal", "script"),
+    ("mathscr", "script"),
+    ("mathfrak", "fraktur"),
+    ("mathsf", "sans-serif"),
+    ("mathtt", "monospace"),
+];
+
+fn lookup(table: &[(&str, &'static str)], name: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(command, _)| *command == name)
+        .map(|(_, value)| *value)
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            chars: input.chars().peekable(),
+            depth: 0,
+            too_deep: false,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    /// Read the name of a command, after the backslash
+    fn command_name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(c) = self.chars.next_if(|c| c.is_ascii_alphabetic()) {
+            name.push(c);
+        }
+        if name.is_empty()
+            && let Some(c) = self.chars.next()
+        {
+            name.push(c);
+        }
+        name
+    }
+
+    /// Read raw text between braces, like the argument of `\text`
+    fn raw_group(&mut self) -> String {
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&'{').is_none() {
+            return self.chars.next().map(String::from).unwrap_or_default();
+        }
+        let mut depth = 0;
+        let mut text = String::new();
+        for c in self.chars.by_ref() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => break,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            text.push(c);
+        }
+        text
+    }
+
+    /// Read an optional argument between brackets, like the index of `\sqrt[3]{x}`
+    fn optional_argument(&mut self) -> Option<Node> {
+        self.skip_whitespace();
+        self.chars.next_if_eq(&'[')?;
+        let mut inner = String::new();
+        for c in self.chars.by_ref() {
+            if c == ']' {
+                break;
+            }
+            inner.push(c);
+        }
+        let mut parser = Parser::new(&inner);
+        parser.depth = self.depth;
+        let nodes = parser.all();
+        self.too_deep |= parser.too_deep;
+        Some(Node::Row(nodes))
+    }
+
+    /// Mandatory argument of a command: a group or a single token
+    fn argument(&mut self) -> Node {
+        self.skip_whitespace();
+        self.atom(false)
+            .unwrap_or_else(|| Node::Error("missing argument".into()))
+    }
+
+    /// Read a delimiter after `\left` or `\right`
+    fn delimiter(&mut self) -> String {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some('.') | None => String::new(),
+            Some('\\') => {
+                let name = self.command_name();
+                lookup(OPERATORS, &name).unwrap_or_default().into()
+            }
+            Some(c) => c.into(),
+        }
+    }
+
+    /// Parse nodes until the end of the input. Tokens ending a row without a matching opening
+    /// token are rendered as errors, the rest of the input is kept.
+    fn all(&mut self) -> Vec<Node> {
+        let mut nodes = vec![];
+        loop {
+            let (row, end) = self.row(false);
+            nodes.extend(row);
+            let unmatched = match end {
+                RowEnd::Input => return nodes,
+                RowEnd::Group => "}".to_string(),
+                RowEnd::Right => format!("\\right{}", self.delimiter()),
+                RowEnd::Environment(name) => format!("\\end{{{name}}}"),
+                // Only ending rows in tables
+                RowEnd::Cell | RowEnd::Line => continue,
+            };
+            println!("Math: unmatched `{unmatched}`");
+            nodes.push(Node::Error(unmatched));
+        }
+    }
+
+    /// Parse nodes until the end of the input, or a token ending the current row
+    fn row(&mut self, in_table: bool) -> (Vec<Node>, RowEnd) {
+        let mut nodes = vec![];
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                None => return (nodes, RowEnd::Input),
+                Some('}') => {
+                    self.chars.next();
+                    return (nodes, RowEnd::Group);
+                }
+                Some('&') if in_table => {
+                    self.chars.next();
+                    return (nodes, RowEnd::Cell);
+                }
+                Some('\\') => {
+                    let mut lookahead = self.chars.clone();
+                    lookahead.next();
+                    let mut name = String::new();
+                    while let Some(c) = lookahead.next_if(|c| c.is_ascii_alphabetic()) {
+                        name.push(c);
+                    }
+                    if name.is_empty() && lookahead.peek() == Some(&'\\') {
+                        self.chars = lookahead;
+                        self.chars.next();
+                        if in_table {
+                            return (nodes, RowEnd::Line);
+                        }
+                        continue;
+                    }
+                    match name.as_str() {
+                        "right" => {
+                            self.chars = lookahead;
+                            return (nodes, RowEnd::Right);
+                        }
+                        "end" => {
+                            self.chars = lookahead;
+                            let name = self.raw_group();
+                            return (nodes, RowEnd::Environment(name));
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+
+            let Some(base) = self.atom(in_table) else {
+                continue;
+            };
+            nodes.push(self.scripts(base));
+        }
+    }
+
+    /// Attach superscripts and subscripts following a node
+    fn scripts(&mut self, base: Node) -> Node {
+        let mut sub = None;
+        let mut sup = None;
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some('_') if sub.is_none() => {
+                    self.chars.next();
+                    sub = Some(Box::new(self.argument()));
+                }
+                Some('^') if sup.is_none() => {
+                    self.chars.next();
+                    sup = Some(Box::new(self.argument()));
+                }
+                Some('\'') if sup.is_none() => {
+                    let mut primes = String::new();
+                    while self.chars.next_if_eq(&'\'').is_some() {
+                        primes.push('′');
+                    }
+                    sup = Some(Box::new(Node::Operator(primes)));
+                }
+                _ => break,
+            }
+        }
+
+        match (sub, sup) {
+            (None, None) => base,
+            (sub, sup) => Node::Scripts {
+                base: Box::new(base),
+                sub,
+                sup,
+            },
+        }
+    }
+
+    /// Parse a single token, group or command, skipping the rest of the input when nested too
+    /// deeply
+    fn atom(&mut self, in_table: bool) -> Option<Node> {
+        if self.depth == MAX_DEPTH {
+            self.too_deep = true;
+            self.chars.by_ref().for_each(drop);
+            return None;
+        }
+        self.depth += 1;
+        let node = self.token(in_table);
+        self.depth -= 1;
+        node
+    }
+
+    fn token(&mut self, in_table: bool) -> Option<Node> {
+        let c = self.chars.next()?;
+        let node = match c {
+            '{' => {
+                let (mut nodes, end) = self.row(in_table);
+                if end == RowEnd::Input {
+                    nodes.push(self.missing("}"));
+                }
+                Node::Row(nodes)
+            }
+            '\\' => self.command(in_table),
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut number = String::from(c);
+                while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+                    number.push(c);
+                }
+                Node::Number(number)
+            }
+            c if c.is_alphabetic() => Node::Identifier(c.into(), None),
+            '-' => Node::Operator("−".into()),
+            '*' => Node::Operator("∗".into()),
+            '~' => Node::Space("0.25em"),
+            '&' => Node::Error("&".into()),
+            c => Node::Operator(c.into()),
+        };
+        Some(node)
+    }
+
+    fn command(&mut self, in_table: bool) -> Node {
+        let name = self.command_name();
+
+        if let Some(letter) = lookup(GREEK, &name) {
+            // Uppercase greek letters are upright
+            let variant = letter
+                .chars()
+                .next()
+                .is_some_and(char::is_uppercase)
+                .then_some("normal");
+            return Node::Identifier(letter.into(), variant);
+        }
+        if let Some(symbol) = lookup(SYMBOLS, &name) {
+            return Node::Identifier(symbol.into(), Some("normal"));
+        }
+        if let Some(operator) = lookup(OPERATORS, &name) {
+            return Node::Operator(operator.into());
+        }
+        if let Some(operator) = lookup(LARGE_OPERATORS, &name) {
+            return Node::Limits(Box::new(Node::Operator(operator.into())));
+        }
+        if let Some(operator) = lookup(INTEGRALS, &name) {
+            return Node::Operator(operator.into());
+        }
+        if FUNCTIONS.contains(&name.as_str()) {
+            return Node::Identifier(name, None);
+        }
+        if LIMIT_FUNCTIONS.contains(&name.as_str()) {
+            return Node::Limits(Box::new(Node::Identifier(name, None)));
+        }
+        if let Some(accent) = lookup(ACCENTS, &name) {
+            return Node::Over(Box::new(self.argument()), accent);
+        }
+        if let Some(width) = lookup(SPACES, &name) {
+            return Node::Space(width);
+        }
+        if let Some(variant) = lookup(FONTS, &name) {
+            let text = self.raw_group();
+            return Node::Identifier(text, Some(variant));
+        }
+
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.argument();
+                let denominator = self.argument();
+                Node::Fraction(Box::new(numerator), Box::new(denominator))
+            }
+            "sqrt" => {
+                let index = self.optional_argument().map(Box::new);
+                Node::Root(Box::new(self.argument()), index)
+            }
+            "text" | "textrm" | "mbox" => Node::Text(self.raw_group()),
+            "underline" => Node::Under(Box::new(self.argument()), "_"),
+            "left" => {
+                let open = self.delimiter();
+                let (mut nodes, end) = self.row(in_table);
+                let close = match end {
+                    RowEnd::Right => self.delimiter(),
+                    _ => {
+                        nodes.push(self.missing("\\right"));
+                        String::new()
+                    }
+                };
+                Node::Fenced(open, nodes, close)
+            }
+            "begin" => self.environment(),
+            name => {
+                println!("Math: unsupported command `\\{name}`");
+                Node::Error(format!("\\{name}"))
+            }
+        }
+    }
+
+    fn environment(&mut self) -> Node {
+        let name = self.raw_group();
+        let (open, close, align) = match name.as_str() {
+            "matrix" | "smallmatrix" => ("", "", "center"),
+            "pmatrix" => ("(", ")", "center"),
+            "bmatrix" => ("[", "]", "center"),
+            "Bmatrix" => ("{", "}", "center"),
+            "vmatrix" => ("|", "|", "center"),
+            "Vmatrix" => ("‖", "‖", "center"),
+            "cases" => ("{", "", "left"),
+            "aligned" | "align" | "align*" | "split" => ("", "", "right left"),
+            "gathered" | "gather" | "gather*" => ("", "", "center"),
+            name => {
+                println!("Math: unsupported environment `{name}`");
+                return Node::Error(format!("\\begin{{{name}}}"));
+            }
+        };
+
+        let mut rows = vec![];
+        let mut cells = vec![];
+        loop {
+            let (mut nodes, end) = self.row(true);
+            if end == RowEnd::Input {
+                nodes.push(self.missing(&format!("\\end{{{name}}}")));
+            }
+            cells.push(Node::Row(nodes));
+            match end {
+                RowEnd::Cell => {}
+                RowEnd::Line => rows.push(std::mem::take(&mut cells)),
+                _ => break,
+            }
+        }
+        // Ignore trailing `\\` at the end of the environment
+        if !(cells.len() == 1 && matches!(&cells[0], Node::Row(nodes) if nodes.is_empty())) {
+            rows.push(cells);
+        }
+
+        Node::Table {
+            rows,
+            align,
+            open,
+            close,
+        }
+    }
+
+    /// Error for a token missing at the end of the input
+    fn missing(&self, token: &str) -> Node {
+        // Input skipped after nesting too deeply is reported once for the whole equation
+        if !self.too_deep {
+            println!("Math: missing `{token}`");
+        }
+        Node::Error(format!("missing {token}"))
+    }
+}
+
+impl Node {
+    fn render(&self, display: bool, output: &mut String) {
+        match self {
+            Node::Identifier(name, variant) => {
+                let name = escape(name);
+                match variant {
+                    Some(variant) => {
+                        output.push_str(&format!("<mi mathvariant=\"{variant}\">{name}</mi>"))
+                    }
+                    None => output.push_str(&format!("<mi>{name}</mi>")),
+                }
+            }
+            Node::Number(number) => output.push_str(&format!("<mn>{}</mn>", escape(number))),
+            Node::Operator(operator) => output.push_str(&format!("<mo>{}</mo>", escape(operator))),
+            Node::Text(text) => output.push_str(&format!("<mtext>{}</mtext>", escape(text))),
+            Node::Space(width) => output.push_str(&format!("<mspace width=\"{width}\" />")),
+            Node::Row(nodes) => {
+                output.push_str("<mrow>");
+                for node in nodes {
+                    node.render(display, output);
+                }
+                output.push_str("</mrow>");
+            }
+            Node::Fraction(numerator, denominator) => {
+                output.push_str("<mfrac>");
+                numerator.render(display, output);
+                denominator.render(display, output);
+                output.push_str("</mfrac>");
+            }
+            Node::Root(radicand, None) => {
+                output.push_str("<msqrt>");
+                radicand.render(display, output);
+                output.push_str("</msqrt>");
+            }
+            Node::Root(radicand, Some(index)) => {
+                output.push_str("<mroot>");
+                radicand.render(display, output);
+                index.render(display, output);
+                output.push_str("</mroot>");
+            }
+            Node::Scripts { base, sub, sup } => {
+                // Large operators get their limits under and over them in display mode
+                let limits = display && matches!(base.as_ref(), Node::Limits(_));
+                let tag = match (limits, sub, sup) {
+                    (true, Some(_), Some(_)) => "munderover",
+                    (true, Some(_), None) => "munder",
+                    (true, None, Some(_)) => "mover",
+                    (false, Some(_), Some(_)) => "msubsup",
+                    (false, Some(_), None) => "msub",
+                    (false, None, Some(_)) => "msup",
+                    (_, None, None) => "mrow",
+                };
+                output.push_str(&format!("<{tag}>"));
+                base.render(display, output);
+                for script in [sub, sup].into_iter().flatten() {
+                    script.render(display, output);
+                }
+                output.push_str(&format!("</{tag}>"));
+            }
+            Node::Over(base, accent) => {
+                output.push_str("<mover accent=\"true\">");
+                base.render(display, output);
+                output.push_str(&format!("<mo>{}</mo></mover>", escape(accent)));
+            }
+            Node::Limits(base) => base.render(display, output),
+            Node::Under(base, accent) => {
+                output.push_str("<munder accentunder=\"true\">");
+                base.render(display, output);
+                output.push_str(&format!("<mo>{}</mo></munder>", escape(accent)));
+            }
+            Node::Fenced(open, nodes, close) => {
+                output.push_str("<mrow>");
+                if !open.is_empty() {
+                    output.push_str(&format!("<mo stretchy=\"true\">{}</mo>", escape(open)));
+                }
+                for node in nodes {
+                    node.render(display, output);
+                }
+                if !close.is_empty() {
+                    output.push_str(&format!("<mo stretchy=\"true\">{}</mo>", escape(close)));
+                }
+                output.push_str("</mrow>");
+            }
+            Node::Table {
+                rows,
+                align,
+                open,
+                close,
+            } => {
+                output.push_str("<mrow>");
+                if !open.is_empty() {
+                    output.push_str(&format!("<mo stretchy=\"true\">{}</mo>", escape(open)));
+                }
+                output.push_str(&format!("<mtable columnalign=\"{align}\">"));
+                for row in rows {
+                    output.push_str("<mtr>");
+                    for cell in row {
+                        output.push_str("<mtd>");
+                        cell.render(display, output);
+                        output.push_str("</mtd>");
+                    }
+                    output.push_str("</mtr>");
+                }
+                output.push_str("</mtable>");
+                if !close.is_empty() {
+                    output.push_str(&format!("<mo stretchy=\"true\">{}</mo>", escape(close)));
+                }
+                output.push_str("</mrow>");
+            }
+            Node::Error(source) => output.push_str(&format!(
+                "<merror><mtext>{}</mtext></merror>",
+                escape(source)
+            )),
+        }
+    }
+}
+
+/// Render LaTeX as MathML, keeping the source as an annotation
+pub(crate) fn to_mathml(tex: &str, display: bool, class: &str) -> String {
+    let mut parser = Parser::new(tex);
+    let mut nodes = parser.all();
+    if parser.too_deep {
+        println!("Math: equation nested more than {MAX_DEPTH} levels deep");
+        nodes = vec![Node::Error("nested too deeply".into())];
+    }
+
+    let mut mathml = String::new();
+    Node::Row(nodes).render(display, &mut mathml);
+
+    let display = if display { "block" } else { "inline" };
+    let class = match class {
+        "" => String::new(),
+        class => format!(" class=\"{class}\""),
+    };
+    format!(
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"{display}\"{class}><semantics>{mathml}<annotation encoding=\"application/x-tex\">{}</annotation></semantics></math>",
+        escape(tex.trim())
+    )
+}
+
+/// Readable fallback for places without MathML support, like feed readers
+pub(crate) fn to_fallback(tex: &str, display: bool) -> String {
+    match display {
+        true => format!("<pre><code>{}</code></pre>", escape(tex.trim())),
+        false => format!("<code>{}</code>", escape(tex.trim())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn math() {
+        for (tex, display, expected) in [
+            (
+                r"\frac{a}{b}",
+                false,
+                "<mfrac><mrow><mi>a</mi></mrow><mrow><mi>b</mi></mrow></mfrac>",
+            ),
+            (
+                r"\sqrt[3]{x}",
+                false,
+                "<mroot><mrow><mi>x</mi></mrow><mrow><mn>3</mn></mrow></mroot>",
+            ),
+            (r"\sqrt x", false, "<msqrt><mi>x</mi></msqrt>"),
+            (
+                "x_i^2",
+                false,
+                "<msubsup><mi>x</mi><mi>i</mi><mn>2</mn></msubsup>",
+            ),
+            ("f'(x)", false, "<msup><mi>f</mi><mo>′</mo></msup>"),
+            (
+                r"\sum_{i=0}^n i",
+                true,
+                "<munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>0</mn></mrow><mi>n</mi></munderover>",
+            ),
+            (r"\sum_{i=0}^n i", false, "<msubsup><mo>∑</mo>"),
+            (
+                r"\lim_{x \to 0} x",
+                true,
+                "<munder><mi>lim</mi><mrow><mi>x</mi><mo>→</mo><mn>0</mn></mrow></munder>",
+            ),
+            (
+                r"\int_0^1 x",
+                true,
+                "<msubsup><mo>∫</mo><mn>0</mn><mn>1</mn></msubsup>",
+            ),
+            (
+                r"\left( \frac{a}{b} \right]",
+                false,
+                r#"<mrow><mo stretchy="true">(</mo><mfrac>"#,
+            ),
+            (
+                r"\left( \frac{a}{b} \right]",
+                false,
+                r#"</mfrac><mo stretchy="true">]</mo></mrow>"#,
+            ),
+            (
+                r"\left. x \right|",
+                false,
+                r#"<mrow><mi>x</mi><mo stretchy="true">|</mo></mrow>"#,
+            ),
+            (
+                r"\begin{pmatrix} a & b \\ c & d \end{pmatrix}",
+                true,
+                r#"<mo stretchy="true">(</mo><mtable columnalign="center"><mtr><mtd><mrow><mi>a</mi></mrow></mtd><mtd><mrow><mi>b</mi></mrow></mtd></mtr><mtr>"#,
+            ),
+            (
+                r"\begin{cases} 1 & x > 0 \\ 0 & \text{otherwise} \end{cases}",
+                true,
+                r#"<mo stretchy="true">{</mo><mtable columnalign="left">"#,
+            ),
+            (
+                r"\begin{cases} 1 & x > 0 \\ 0 & \text{otherwise} \end{cases}",
+                true,
+                "<mo>&gt;</mo>",
+            ),
+            (
+                r"\begin{foo} a \end{foo}",
+                true,
+                r"<merror><mtext>\begin{foo}</mtext></merror><mi>a</mi><merror><mtext>\end{foo}</mtext></merror>",
+            ),
+            (
+                r"\unknown x",
+                false,
+                r"<merror><mtext>\unknown</mtext></merror><mi>x</mi>",
+            ),
+            (
+                r"\alpha + \Gamma",
+                false,
+                r#"<mi>α</mi><mo>+</mo><mi mathvariant="normal">Γ</mi>"#,
+            ),
+            (
+                r"\hat{x} \mathbf{v}",
+                false,
+                r#"<mover accent="true"><mrow><mi>x</mi></mrow><mo>^</mo></mover><mi mathvariant="bold">v</mi>"#,
+            ),
+            (
+                r"a \leq b \, c",
+                false,
+                r#"<mo>≤</mo><mi>b</mi><mspace width="0.1667em" />"#,
+            ),
+        ] {
+            let mathml = to_mathml(tex, display, "");
+            assert!(mathml.contains(expected), "`{tex}` rendered as {mathml}");
+        }
+    }
+
+    #[test]
+    fn math_unbalanced() {
+        for (tex, error) in [
+            ("{a", "missing }"),
+            (r"\frac{a}", "missing argument"),
+            (r"\left( a", r"missing \right"),
+            (r"\begin{matrix} a & b", r"missing \end{matrix}"),
+        ] {
+            let mathml = to_mathml(tex, false, "");
+            assert!(
+                mathml.contains(&format!("<merror><mtext>{error}</mtext></merror>")),
+                "`{tex}` rendered as {mathml}"
+            );
+        }
+    }
+
+    #[test]
+    fn math_unmatched_closers() {
+        for (tex, error, rest) in [
+            ("a } b + c", "}", "<mi>c</mi>"),
+            (r"x = \end{foo} y", r"\end{foo}", "<mi>y</mi>"),
+            (r"a \right) b", r"\right)", "<mi>b</mi>"),
+            (r"\sqrt[3}]{x} y", "}", "<mi>y</mi>"),
+        ] {
+            let mathml = to_mathml(tex, false, "");
+            assert!(
+                mathml.contains(&format!("<merror><mtext>{error}</mtext></merror>")),
+                "{mathml}"
+            );
+            assert!(mathml.contains(rest), "{mathml}");
+        }
+    }
+
+    #[test]
+    fn math_nested_too_deeply() {
+        let nested = format!("{}x{}", "{".repeat(20), "}".repeat(20));
+        assert!(to_mathml(&nested, false, "").contains("<mi>x</mi>"));
+
+        for tex in [
+            "{".repeat(100_000),
+            r"\frac".repeat(100_000),
+            r"\sqrt[".repeat(100_000),
+            r"\left(".repeat(100_000),
+            r"\begin{matrix}".repeat(100_000),
+        ] {
+            let mathml = to_mathml(&tex, false, "");
+            assert!(
+                mathml.contains("<merror><mtext>nested too deeply</mtext></merror>"),
+                "{}",
+                &mathml[..200]
+            );
+        }
+    }
+}
@@ -91,11 +91,9 @@ impl MetadataList {
 
         for event in markdown_events {
             match event {
-                Event::Text(text) => {
-                    if parse_text {
-                        for line in text.lines() {
-                            list.push(Metadata::from_str(line)?);
-                        }
+                Event::Text(text) if parse_text => {
+                    for line in text.lines() {
+                        list.push(Metadata::from_str(line)?);
                     }
                 }
                 Event::End(TagEnd::MetadataBlock(MetadataBlockKind::PlusesStyle)) => {
@@ -159,13 +157,10 @@ impl MetadataList {
             .expect("there should be a `Metadata::Uuid`")
     }
 
-    pub fn description(&self) -> &str {
-        self.0
-            .iter()
-            .find_map(|el| match el {
-                Metadata::Description(description) => Some(description.as_ref()),
-                _ => None,
-            })
-            .expect("there should be a `Metadata::Description`")
+    pub fn description(&self) -> Option<&str> {
+        self.0.iter().find_map(|el| match el {
+            Metadata::Description(description) => Some(description.as_ref()),
+            _ => None,
+        })
     }
 }
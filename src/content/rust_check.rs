@@ -0,0 +1,181 @@
+//! Compile-check `rust` code blocks with the local toolchain, like doc tests.
+//!
+//! Lines starting with `# ` are compiled but not shown, and code without a `main` function is
+//! wrapped in one.
+
+use super::code_block::CodeBlockInfo;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Compile `rust` code blocks during the build, failing it when one does not behave as
+/// annotated: `ignore` is skipped, `no_run` must compile, `should_panic` must panic, and
+/// `compile_fail` must not compile. Other blocks must run successfully.
+#[derive(Debug, Clone, Copy)]
+pub struct RustCheck {
+    /// Add a link opening code blocks in the Rust playground
+    pub playground_link: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Mode {
+    Ignore,
+    Run,
+    NoRun,
+    ShouldPanic,
+    CompileFail,
+}
+
+impl Mode {
+    pub fn from_info(info: &CodeBlockInfo) -> Self {
+        match info {
+            info if info.flag("ignore") => Self::Ignore,
+            info if info.flag("compile_fail") => Self::CompileFail,
+            info if info.flag("no_run") => Self::NoRun,
+            info if info.flag("should_panic") => Self::ShouldPanic,
+            _ => Self::Run,
+        }
+    }
+}
+
+/// Whether a code block is written in rust
+pub(crate) fn is_rust(info: &CodeBlockInfo) -> bool {
+    info.language.as_deref() == Some("rust")
+}
+
+/// Line hidden from the page, with its code
+fn hidden_line(line: &str) -> Option<&str> {
+    match line.trim_start() {
+        "#" => Some(""),
+        trimmed => trimmed.strip_prefix("# "),
+    }
+}
+
+/// `##` escapes a line starting with `#`
+fn unescaped(line: &str) -> String {
+    let trimmed = line.trim_start();
+    match trimmed.strip_prefix("##") {
+        Some(rest) => format!("{}#{rest}", &line[..line.len() - trimmed.len()]),
+        None => line.to_string(),
+    }
+}
+
+/// Code shown on the page, without hidden lines
+pub(crate) fn visible(code: &str) -> String {
+    code.lines()
+        .filter(|line| hidden_line(line).is_none())
+        .map(|line| unescaped(line) + "\n")
+        .collect()
+}
+
+/// Program compiled from a code block, wrapped in a `main` function when it has none
+pub(crate) fn program(code: &str) -> String {
+    let code = code
+        .lines()
+        .map(|line| match hidden_line(line) {
+            Some(hidden) => hidden.to_string(),
+            None => unescaped(line),
+        })
+        .collect::<Vec<_>>();
+
+    // Crate attributes stay at the top of the program
+    let (attributes, body): (Vec<String>, Vec<String>) = code
+        .into_iter()
+        .partition(|line| line.trim_start().starts_with("#!["));
+
+    let mut program = String::from("#![allow(unused)]\n");
+    attributes.iter().for_each(|line| {
+        program.push_str(line);
+        program.push('\n');
+    });
+    let body = body.join("\n");
+    match body.contains("fn main") {
+        true => program.push_str(&body),
+        false => program.push_str(&format!("fn main() {{\n{body}\n}}")),
+    }
+    program.push('\n');
+    program
+}
+
+/// Link opening a program in the Rust playground
+pub(crate) fn playground_url(program: &str) -> String {
+    format!(
+        "https://play.rust-lang.org/?version=stable&mode=debug&edition=2024&code={}",
+        percent_encoding::utf8_percent_encode(program, percent_encoding::NON_ALPHANUMERIC)
+    )
+}
+
+/// Compile and run a program as annotated, returning what went wrong
+pub(crate) fn check(program: &str, mode: Mode) -> Result<(), String> {
+    if mode == Mode::Ignore {
+        return Ok(());
+    }
+
+    // Every check gets its own directory
+    static CHECKS: AtomicUsize = AtomicUsize::new(0);
+    let directory = std::env::temp_dir().join(format!(
+        "leptos_ssg-rust-check-{}-{}",
+        std::process::id(),
+        CHECKS.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = compile_and_run(&directory, program, mode);
+    let _ = std::fs::remove_dir_all(&directory);
+    result
+}
+
+fn compile_and_run(directory: &std::path::Path, program: &str, mode: Mode) -> Result<(), String> {
+    std::fs::create_dir_all(directory)
+        .map_err(|e| format!("failed to create `{}`: {e}", directory.display()))?;
+    let source = directory.join("main.rs");
+    std::fs::write(&source, program)
+        .map_err(|e| format!("failed to write `{}`: {e}", source.display()))?;
+    let executable = directory.join("main");
+
+    let rustc = std::env::var("RUSTC").unwrap_or("rustc".into());
+    let mut command = Command::new(&rustc);
+    command
+        .args([
+            "--edition",
+            "2024",
+            "--crate-type",
+            "bin",
+            "--crate-name",
+            "code_block",
+        ])
+        .arg("-o")
+        .arg(&executable)
+        .arg(&source);
+    if mode == Mode::CompileFail {
+        command.arg("--emit=metadata");
+    }
+    let compiled = command
+        .output()
+        .map_err(|e| format!("failed to run `{rustc}`: {e}"))?;
+
+    match (mode, compiled.status.success()) {
+        (Mode::CompileFail, true) => return Err("compiled, but is marked `compile_fail`".into()),
+        (Mode::CompileFail, false) => return Ok(()),
+        (_, false) => {
+            return Err(format!(
+                "failed to compile:\n{}",
+                String::from_utf8_lossy(&compiled.stderr)
+            ));
+        }
+        (Mode::NoRun, true) => return Ok(()),
+        (_, true) => {}
+    }
+
+    let ran = Command::new(&executable)
+        .output()
+        .map_err(|e| format!("failed to run: {e}"))?;
+    // Panics exit with code 101
+    match (mode, ran.status.code()) {
+        (Mode::ShouldPanic, Some(101)) => Ok(()),
+        (Mode::ShouldPanic, _) => Err("did not panic, but is marked `should_panic`".into()),
+        (_, Some(0)) => Ok(()),
+        _ => Err(format!(
+            "failed when running ({}):\n{}",
+            ran.status,
+            String::from_utf8_lossy(&ran.stderr)
+        )),
+    }
+}
@@ -0,0 +1,40 @@
+//! Sanitize raw HTML written in markdown, keeping an allow-list of tags and attributes
+
+/// Sanitize a complete HTML block
+pub(crate) fn block(html: &str) -> String {
+    ammonia::clean(html)
+}
+
+/// Sanitize inline HTML. Markdown emits each inline tag on its own, opening and closing tags of
+/// an element are sanitized separately.
+pub(crate) fn inline(html: &str) -> String {
+    let trimmed = html.trim();
+
+    // Closing tags are kept as long as their element is allowed
+    if let Some(name) = trimmed
+        .strip_prefix("</")
+        .and_then(|tag| tag.strip_suffix('>'))
+    {
+        let name = name.trim().to_lowercase();
+        return match ammonia::Builder::default()
+            .clone_tags()
+            .contains(name.as_str())
+        {
+            true => format!("</{name}>"),
+            false => String::new(),
+        };
+    }
+
+    // The sanitizer closes elements it keeps, their closing tag is another event
+    let sanitized = ammonia::clean(html);
+    let name = trimmed
+        .trim_start_matches('<')
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    match sanitized.strip_suffix(&format!("</{name}>")) {
+        Some(opening_tag) => opening_tag.to_string(),
+        None => sanitized,
+    }
+}
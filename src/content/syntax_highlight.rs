@@ -0,0 +1,104 @@
+use std::sync::LazyLock;
+use syntect::highlighting::ThemeSet;
+use syntect::html::{ClassStyle, css_for_theme_with_class_style, line_tokens_to_classed_spans};
+use syntect::parsing::{ParseState, ScopeStack, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+/// Syntaxes of `bat`, a superset of the defaults of syntect with languages such as TOML and Nix
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(two_face::syntax::extra_newlines);
+static THEME_SET: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+const THEME_LIGHT: &str = "InspiredGitHub";
+const THEME_DARK: &str = "base16-ocean.dark";
+
+/// Class to put on the element containing highlighted code, to get colors of the themes
+pub(crate) const CODE_CLASS: &str = "hl-code";
+
+/// Highlight code in HTML, using classes from the stylesheet, line by line.
+/// Each line is balanced, without its line ending. Unknown languages fall back to plain text.
+pub(crate) fn highlight_lines(
+    code: &str,
+    language: Option<&str>,
+) -> Result<Vec<String>, syntect::Error> {
+    let syntax = match language {
+        Some(language) => SYNTAX_SET
+            .find_syntax_by_token(language)
+            .unwrap_or_else(|| {
+                println!(
+                    "Syntax highlight: unknown language `{language}`, falling back to plain text"
+                );
+                SYNTAX_SET.find_syntax_plain_text()
+            }),
+        None => SYNTAX_SET.find_syntax_plain_text(),
+    };
+
+    let mut parse_state = ParseState::new(syntax);
+    let mut scope_stack = ScopeStack::new();
+    // Spans still open at the end of the previous line, opened again on the next one
+    let mut open_spans: Vec<String> = vec![];
+
+    LinesWithEndings::from(code)
+        .map(|line| {
+            let ops = parse_state.parse_line(line, &SYNTAX_SET)?;
+            let (mut html, _) =
+                line_tokens_to_classed_spans(line, &ops, CLASS_STYLE, &mut scope_stack)?;
+
+            // The line ending may be inside a span
+            if let Some(idx) = html.rfind('\n') {
+                html.remove(idx);
+                if html[..idx].ends_with('\r') {
+                    html.remove(idx - 1);
+                }
+            }
+
+            let mut balanced = open_spans.concat();
+            balanced.push_str(&html);
+            let mut rest = html.as_str();
+            while let Some(idx) = rest.find('<') {
+                rest = &rest[idx..];
+                let end = rest.find('>').map(|end| end + 1).unwrap_or(rest.len());
+                match rest.starts_with("</") {
+                    true => _ = open_spans.pop(),
+                    false => open_spans.push(rest[..end].to_string()),
+                }
+                rest = &rest[end..];
+            }
+            balanced.push_str(&"</span>".repeat(open_spans.len()));
+
+            Ok(balanced)
+        })
+        .collect()
+}
+
+/// Stylesheet with the light theme, and the dark theme when the browser prefers it
+pub(crate) fn stylesheet() -> &'static str {
+    static STYLESHEET: LazyLock<String> = LazyLock::new(|| {
+        let light = css_for_theme_with_class_style(&THEME_SET.themes[THEME_LIGHT], CLASS_STYLE)
+            .expect("css for light theme");
+        let dark = css_for_theme_with_class_style(&THEME_SET.themes[THEME_DARK], CLASS_STYLE)
+            .expect("css for dark theme");
+
+        format!("{light}\n@media (prefers-color-scheme: dark) {{\n{dark}\n}}")
+    });
+
+    &STYLESHEET
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn languages() {
+        for language in ["rust", "toml", "nix", "sh", "js", "yaml"] {
+            assert!(
+                SYNTAX_SET.find_syntax_by_token(language).is_some(),
+                "`{language}` is not highlighted"
+            );
+        }
+
+        let lines = highlight_lines("[package]\nname = \"leptos_ssg\"", Some("toml")).unwrap();
+        assert!(lines[1].contains("hl-string"), "{lines:?}");
+    }
+}
@@ -0,0 +1,117 @@
+use pulldown_cmark::{Event, Tag, TagEnd};
+use std::borrow::Cow;
+
+/// Typographic rules applied to the text of content and to titles, code is left untouched
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Typography {
+    /// Curly quotes, dashes from `--` and `---`, and ellipsis from `...`
+    pub smart_punctuation: bool,
+    /// Spaces that can not break a line, depending on the language
+    pub non_breaking_spaces: Option<NonBreakingSpaces>,
+}
+
+/// Rules for non-breaking spaces
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NonBreakingSpaces {
+    /// Narrow non-breaking space before `;`, `!`, `?` and `»`, and after `«`, non-breaking space
+    /// before `:`
+    French,
+}
+
+const NO_BREAK_SPACE: char = '\u{a0}';
+const NARROW_NO_BREAK_SPACE: char = '\u{202f}';
+
+impl NonBreakingSpaces {
+    /// Replace spaces next to punctuation, spaces are not added where there are none
+    pub(crate) fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::French => {
+                if !text.contains([':', ';', '!', '?', '«', '»']) {
+                    return Cow::Borrowed(text);
+                }
+
+                let mut output = String::with_capacity(text.len());
+                let mut chars = text.chars().peekable();
+                while let Some(c) = chars.next() {
+                    match (c, chars.peek()) {
+                        (' ', Some(':')) => output.push(NO_BREAK_SPACE),
+                        (' ', Some(';' | '!' | '?' | '»')) => output.push(NARROW_NO_BREAK_SPACE),
+                        ('«', Some(' ')) => {
+                            chars.next();
+                            output.push('«');
+                            output.push(NARROW_NO_BREAK_SPACE);
+                        }
+                        (c, _) => output.push(c),
+                    }
+                }
+                Cow::Owned(output)
+            }
+        }
+    }
+}
+
+impl Typography {
+    /// Apply rules to text events of markdown, outside of code blocks
+    pub(crate) fn apply_to_events<'a>(&self, events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+        let Some(non_breaking_spaces) = self.non_breaking_spaces else {
+            return events;
+        };
+
+        let mut code_block = false;
+        events
+            .into_iter()
+            .map(|event| match event {
+                Event::Start(Tag::CodeBlock(_) | Tag::MetadataBlock(_)) => {
+                    code_block = true;
+                    event
+                }
+                Event::End(TagEnd::CodeBlock | TagEnd::MetadataBlock(_)) => {
+                    code_block = false;
+                    event
+                }
+                Event::Text(text) if !code_block => {
+                    Event::Text(non_breaking_spaces.apply(&text).into_owned().into())
+                }
+                event => event,
+            })
+            .collect()
+    }
+
+    /// Apply rules to plain text, such as titles and descriptions
+    pub(crate) fn apply_to_plain_text(&self, text: &str) -> String {
+        let text = match self.smart_punctuation {
+            true => smart_punctuation(text),
+            false => text.to_string(),
+        };
+
+        match self.non_breaking_spaces {
+            Some(non_breaking_spaces) => non_breaking_spaces.apply(&text).into_owned(),
+            None => text,
+        }
+    }
+}
+
+/// Smart punctuation of plain text, like markdown does for content
+fn smart_punctuation(text: &str) -> String {
+    let text = text
+        .replace("---", "\u{2014}")
+        .replace("--", "\u{2013}")
+        .replace("...", "\u{2026}");
+
+    // Quotes open at the start of words, and close everywhere else
+    let mut output = String::with_capacity(text.len());
+    let mut previous: Option<char> = None;
+    for c in text.chars() {
+        let opening =
+            previous.is_none_or(|p| p.is_whitespace() || "([{\u{2014}\u{2013}".contains(p));
+        output.push(match (c, opening) {
+            ('"', true) => '\u{201c}',
+            ('"', false) => '\u{201d}',
+            ('\'', true) => '\u{2018}',
+            ('\'', false) => '\u{2019}',
+            (c, _) => c,
+        });
+        previous = Some(c);
+    }
+    output
+}
@@ -1,3 +1,4 @@
+use crate::content::GenerateHtmlError;
 use atom_syndication::*;
 
 fn jiff_to_chrono_date(zoned: &jiff::Zoned) -> chrono::DateTime<chrono::FixedOffset> {
@@ -6,7 +7,23 @@ fn jiff_to_chrono_date(zoned: &jiff::Zoned) -> chrono::DateTime<chrono::FixedOff
     chrono::DateTime::parse_from_rfc3339(&rfc3339_date).unwrap()
 }
 
-pub fn create_feed(config: &crate::BuildConfig, content: &[crate::content::Content]) -> Feed {
+/// Identifier of content in feeds, the same in every format.
+///
+/// UUID is constructed with:
+/// - blog UUID
+/// - UUID of content: a UUID is required for every piece of content
+fn content_id(config: &crate::BuildConfig, content: &crate::content::Content) -> String {
+    format!(
+        "urn:uuid:{}",
+        uuid::Uuid::new_v5(config.feed_uuid.as_ref(), content.meta().uuid().as_ref())
+            .as_hyphenated()
+    )
+}
+
+pub fn create_feed(
+    config: &crate::BuildConfig,
+    content: &[crate::content::Content],
+) -> Result<Feed, GenerateHtmlError> {
     let absolute_url = config.absolute_url();
 
     let mut feed = FeedBuilder::default();
@@ -55,18 +72,12 @@ pub fn create_feed(config: &crate::BuildConfig, content: &[crate::content::Conte
         .map(|content| {
             let mut entry = EntryBuilder::default();
             entry.author(author.clone());
-            entry.title(content.meta().title());
+            entry.title(content.title(config));
             entry.published(jiff_to_chrono_date(content.meta().datetime()));
             entry.updated(jiff_to_chrono_date(content.meta().datetime()));
+            entry.summary(content.description(config).map(Text::plain));
 
-            // UUID is constructed with:
-            // - blog UUID
-            // - UUID of content: a UUID is required for every piece of content
-            entry.id(format!(
-                "urn:uuid:{}",
-                uuid::Uuid::new_v5(config.feed_uuid.as_ref(), content.meta().uuid().as_ref())
-                    .as_hyphenated()
-            ));
+            entry.id(content_id(config, content));
 
             // URL
             let absolute_absolute_url = format!("{absolute_url}{}/", content.slug());
@@ -80,14 +91,161 @@ pub fn create_feed(config: &crate::BuildConfig, content: &[crate::content::Conte
             let mut content_feed = ContentBuilder::default();
             content_feed.lang(Some(crate::LANG.into()));
             content_feed.content_type(Some("html".into()));
-            content_feed.value(content.raw_html(&absolute_url));
+            content_feed.value(content.raw_html(config)?);
             entry.content(Some(content_feed.build()));
 
-            entry.build()
+            Ok(entry.build())
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, _>>()?;
 
     feed.entries(entries);
 
-    feed.build()
+    Ok(feed.build())
+}
+
+/// RSS 2.0 feed, for readers which do not support Atom
+pub fn create_rss_feed(
+    config: &crate::BuildConfig,
+    content: &[crate::content::Content],
+) -> Result<rss::Channel, GenerateHtmlError> {
+    let absolute_url = config.absolute_url();
+
+    let mut channel = rss::ChannelBuilder::default();
+
+    channel.title(config.website_name);
+    channel.link(&absolute_url);
+    channel.description(config.website_tagline);
+    channel.language(Some(crate::LANG.into()));
+    channel.generator(Some(format!(
+        "{} {}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    )));
+
+    let mut link_rss = LinkBuilder::default();
+    link_rss
+        .href(format!("{absolute_url}rss.xml"))
+        .rel("self")
+        .mime_type(Some("application/rss+xml".into()));
+    channel.atom_ext(Some(
+        rss::extension::atom::AtomExtensionBuilder::default()
+            .links(vec![link_rss.build()])
+            .build(),
+    ));
+
+    if let Some(content) = content.first() {
+        let date = jiff_to_chrono_date(content.meta().datetime()).to_rfc2822();
+        channel.pub_date(Some(date.clone()));
+        channel.last_build_date(Some(date));
+    }
+
+    // RSS requires an email address for authors
+    let author = rss::extension::dublincore::DublinCoreExtensionBuilder::default()
+        .creators(vec![config.content_author.to_string()])
+        .build();
+
+    let items = content
+        .iter()
+        .map(|content| {
+            let mut item = rss::ItemBuilder::default();
+            item.dublin_core_ext(Some(author.clone()));
+            item.title(Some(content.title(config)));
+            item.link(Some(format!("{absolute_url}{}/", content.slug())));
+            item.pub_date(Some(
+                jiff_to_chrono_date(content.meta().datetime()).to_rfc2822(),
+            ));
+            item.description(content.description(config));
+            item.content(Some(content.raw_html(config)?));
+
+            // Same identifier as the Atom feed, which is not a link
+            let mut guid = rss::GuidBuilder::default();
+            guid.value(content_id(config, content)).permalink(false);
+            item.guid(Some(guid.build()));
+
+            Ok(item.build())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    channel.items(items);
+
+    Ok(channel.build())
+}
+
+/// JSON Feed 1.1, see <https://www.jsonfeed.org/version/1.1/>
+#[derive(Debug, serde::Serialize)]
+pub struct JsonFeed {
+    version: &'static str,
+    title: String,
+    home_page_url: String,
+    feed_url: String,
+    description: String,
+    language: &'static str,
+    authors: Vec<JsonFeedAuthor>,
+    items: Vec<JsonFeedItem>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct JsonFeedAuthor {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct JsonFeedItem {
+    id: String,
+    url: String,
+    title: String,
+    content_html: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    date_published: String,
+    date_modified: String,
+    authors: Vec<JsonFeedAuthor>,
+}
+
+/// JSON Feed, for scripts and readers preferring JSON
+pub fn create_json_feed(
+    config: &crate::BuildConfig,
+    content: &[crate::content::Content],
+) -> Result<JsonFeed, GenerateHtmlError> {
+    let absolute_url = config.absolute_url();
+
+    let author = JsonFeedAuthor {
+        name: config.content_author.to_string(),
+        url: config.external_url.map(ToString::to_string),
+    };
+
+    let items = content
+        .iter()
+        .map(|content| {
+            let date = content
+                .meta()
+                .datetime()
+                .strftime(crate::RFC_3339_FORMAT)
+                .to_string();
+
+            Ok(JsonFeedItem {
+                id: content_id(config, content),
+                url: format!("{absolute_url}{}/", content.slug()),
+                title: content.title(config),
+                content_html: content.raw_html(config)?,
+                summary: content.description(config),
+                date_published: date.clone(),
+                date_modified: date,
+                authors: vec![author.clone()],
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: config.website_name.to_string(),
+        feed_url: format!("{absolute_url}feed.json"),
+        home_page_url: absolute_url,
+        description: config.website_tagline.to_string(),
+        language: crate::LANG,
+        authors: vec![author],
+        items,
+    })
 }
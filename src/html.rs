@@ -4,7 +4,7 @@ pub mod prelude {
     #[cfg(feature = "opengraph")]
     pub use super::add_opengraph_property;
 
-    pub use super::underline_link;
+    pub use super::{external_link_icon, underline_external_link, underline_link};
     pub use leptos::prelude::*;
     pub use tailwind_fuse::tw_join;
     pub use tailwind_fuse::tw_merge;
@@ -12,60 +12,179 @@ pub mod prelude {
 mod icons;
 
 use crate::config::BuildConfig;
-use jiff::Timestamp;
 use prelude::*;
 
-pub fn syntax_highlight(languages: impl Iterator<Item = impl AsRef<str>>) -> impl IntoView {
-    const HIGHLIGHT_JS_URL: &str = "https://unpkg.com/@highlightjs/cdn-assets@11.11.1";
+/// Stylesheet for code blocks highlighted during the build
+pub fn syntax_highlight() -> impl IntoView {
+    view! { <style inner_html=crate::content::syntax_highlight::stylesheet()></style> }
+}
 
-    // Load core highlight.js
-    let load_js = format!("import hljs from \"{HIGHLIGHT_JS_URL}/es/core.min.js\";");
+/// Decode blurhash placeholders of images still loading, and fade images in over them
+pub fn blurhash_decoder() -> impl IntoView {
+    let blurhash_decoder = r#"
+const characters = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+const decode83 = (string) => [...string].reduce((value, character) => value * 83 + characters.indexOf(character), 0);
+const srgbToLinear = (value) => {
+  const v = value / 255;
+  return v <= 0.04045 ? v / 12.92 : Math.pow((v + 0.055) / 1.055, 2.4);
+};
+const linearToSrgb = (value) => {
+  const v = Math.max(0, Math.min(1, value));
+  return Math.round((v <= 0.0031308 ? v * 12.92 : 1.055 * Math.pow(v, 1 / 2.4) - 0.055) * 255);
+};
+const signPow = (value, exponent) => Math.sign(value) * Math.pow(Math.abs(value), exponent);
+
+function decodeBlurhash(blurhash, width, height) {
+  const sizeFlag = decode83(blurhash[0]);
+  const componentsY = Math.floor(sizeFlag / 9) + 1;
+  const componentsX = (sizeFlag % 9) + 1;
+  const maximumValue = (decode83(blurhash[1]) + 1) / 166;
+
+  const dc = decode83(blurhash.substring(2, 6));
+  const colors = [[srgbToLinear(dc >> 16), srgbToLinear((dc >> 8) & 255), srgbToLinear(dc & 255)]];
+  for (let i = 1; i < componentsX * componentsY; i++) {
+    const ac = decode83(blurhash.substring(4 + i * 2, 6 + i * 2));
+    colors.push([Math.floor(ac / 361), Math.floor(ac / 19) % 19, ac % 19]
+      .map((quantized) => signPow((quantized - 9) / 9, 2) * maximumValue));
+  }
 
-    // Make sure every language appears only once
-    let languages = languages
-        .map(|language| language.as_ref().to_string())
-        .collect::<std::collections::HashSet<_>>();
+  const pixels = new Uint8ClampedArray(width * height * 4);
+  for (let y = 0; y < height; y++) {
+    for (let x = 0; x < width; x++) {
+      const pixel = [0, 0, 0];
+      for (let j = 0; j < componentsY; j++) {
+        for (let i = 0; i < componentsX; i++) {
+          const basis = Math.cos((Math.PI * x * i) / width) * Math.cos((Math.PI * y * j) / height);
+          colors[i + j * componentsX].forEach((color, channel) => pixel[channel] += color * basis);
+        }
+      }
+      pixels.set([...pixel.map(linearToSrgb), 255], 4 * (x + y * width));
+    }
+  }
+  return pixels;
+}
 
-    // Load js file for every language, and register it to highlight.js
-    let languages = languages.into_iter().map(|l| {
-        format!("import {l} from \"{HIGHLIGHT_JS_URL}/es/languages/{l}.min.js\"; hljs.registerLanguage(\"{l}\", {l});")
-    }).collect::<Vec<_>>().join("\n");
+document.querySelectorAll("img[data-blurhash]").forEach((img) => {
+  if (img.complete) {
+    return;
+  }
 
-    // Highlight code blocks, and remove placeholder CSS while highlight.js was loading
-    let highlight_blocks = r#"
-document.addEventListener("DOMContentLoaded", (event) => {
-  document.querySelectorAll("pre code").forEach((el) => hljs.highlightElement(el));
-  document.querySelectorAll("pre").forEach((el) => {
-    el.removeAttribute("class");
+  const canvas = document.createElement("canvas");
+  canvas.width = canvas.height = 32;
+  const context = canvas.getContext("2d");
+  const imageData = context.createImageData(canvas.width, canvas.height);
+  imageData.data.set(decodeBlurhash(img.dataset.blurhash, canvas.width, canvas.height));
+  context.putImageData(imageData, 0, 0);
+
+  // Placeholder behind the image, taking the place of its margins
+  const target = img.parentElement.tagName === "PICTURE" ? img.parentElement : img;
+  const wrapper = document.createElement("span");
+  wrapper.style.display = "block";
+  wrapper.style.width = "fit-content";
+  wrapper.style.maxWidth = "100%";
+  wrapper.style.margin = getComputedStyle(img).margin;
+  wrapper.style.background = `url(${canvas.toDataURL()}) center / cover no-repeat`;
+  img.style.margin = "0";
+  target.replaceWith(wrapper);
+  wrapper.append(target);
+
+  img.style.opacity = "0";
+  img.addEventListener("load", () => {
+    img.style.transition = "opacity 0.4s";
+    img.style.opacity = "1";
+    // Transparent parts of the image should not show the placeholder
+    img.addEventListener("transitionend", () => wrapper.style.background = "", { once: true });
+  }, { once: true });
+  img.addEventListener("error", () => img.style.opacity = "1", { once: true });
+});"#;
 
-    // Add padding for scrollbar
-    el.classList.add("pb-4");
+    view! { <script type="module" inner_html=blurhash_decoder></script> }
+}
+
+/// Add a button copying code blocks to the clipboard, when the browser allows it
+pub fn code_copy_button() -> impl IntoView {
+    let code_copy_button = r#"
+if (navigator.clipboard) {
+  document.querySelectorAll("pre > code").forEach((code) => {
+    // Scrolling code blocks should not move the button
+    const pre = code.parentElement;
+    const wrapper = document.createElement("div");
+    wrapper.className = "relative";
+    pre.replaceWith(wrapper);
+    wrapper.append(pre);
+
+    const button = document.createElement("button");
+    button.type = "button";
+    button.textContent = "Copy";
+    button.className = "absolute top-2 right-2 px-2 py-1 text-sm font-mono bg-gray-400 dark:bg-gray-600 opacity-75 hover:opacity-100";
+    button.addEventListener("click", async () => {
+      // Line numbers are not part of the code
+      const copy = code.cloneNode(true);
+      copy.querySelectorAll("[data-line-number]").forEach((lineNumber) => lineNumber.remove());
+      try {
+        await navigator.clipboard.writeText(copy.textContent);
+        button.textContent = "Copied";
+      } catch {
+        button.textContent = "Failed to copy";
+      }
+      setTimeout(() => button.textContent = "Copy", 2000);
+    });
+    wrapper.append(button);
   });
-});"#;
+}"#;
 
-    let syntax_highlight = [load_js, languages, highlight_blocks.into()].join("\n");
+    view! { <script type="module" inner_html=code_copy_button></script> }
+}
 
+pub fn underline_link(
+    url: impl ToString,
+    children: impl IntoAny,
+    class: Option<&'static str>,
+) -> impl IntoView {
+    let class = class.unwrap_or_default();
+    let class = tw_merge!("underline", "text-sky-900", "dark:text-yellow-400", class);
     view! {
-        <link rel="stylesheet" href=format!("{HIGHLIGHT_JS_URL}/styles/default.min.css") />
-        <script type="module" inner_html=syntax_highlight></script>
+        <a href=url.to_string() class=class>
+            {children.into_any()}
+        </a>
     }
+    .into_view()
 }
 
-pub fn underline_link(
+/// Link with underline, following the policy of the build when it points outside of the website
+pub fn underline_external_link(
+    config: &BuildConfig,
     url: impl ToString,
     children: impl IntoAny,
     class: Option<&'static str>,
 ) -> impl IntoView {
+    let url = url.to_string();
+    let policy = config.external_link_policy(&url);
     let class = class.unwrap_or_default();
     let class = tw_merge!("underline", "text-sky-900", "dark:text-yellow-400", class);
     view! {
-        <a href=url.to_string() class=class>
+        <a
+            href=url
+            rel=policy.map(|policy| policy.rel())
+            target=policy.and_then(|policy| policy.target)
+            class=class
+        >
             {children.into_any()}
+            {policy.filter(|policy| policy.icon).map(|_| external_link_icon())}
         </a>
     }
     .into_view()
 }
 
+/// Icon shown after the text of links pointing outside of the website
+pub fn external_link_icon() -> impl IntoView {
+    view! {
+        <span aria-hidden="true" data-external-link>
+            {icon_arrow_top_right_on_square(Some(tw_join!("inline", "size-4", "ml-0.5", "align-text-top").to_string()))}
+        </span>
+    }
+}
+
 pub fn navigation(children: impl IntoAny) -> impl IntoView {
     view! {
         <nav>
@@ -76,11 +195,12 @@ pub fn navigation(children: impl IntoAny) -> impl IntoView {
     }
 }
 
-fn footer(timestamp: &Timestamp) -> impl IntoView {
+fn footer(config: &BuildConfig) -> impl IntoView {
+    let timestamp = config.timestamp;
     view! {
         <footer class=tw_join!("bg-black")>
             <div class=tw_join!("container", "mx-auto", "py-8", "px-4", "sm:px-8", "md:px-16", "lg:px-32", "xl:px-64", "2xl:px-96")>
-                <p class=tw_join!("text-white")>"Page generated with "{underline_link("https://github.com/deadbaed/leptos_ssg", "leptos_ssg", Some("text-yellow-400"))}" on "{timestamp.to_string()}" "<span data-relative-timestamp={timestamp.as_millisecond()}></span></p>
+                <p class=tw_join!("text-white")>"Page generated with "{underline_external_link(config, "https://github.com/deadbaed/leptos_ssg", "leptos_ssg", Some("text-yellow-400"))}" on "{timestamp.to_string()}" "<span data-relative-timestamp={timestamp.as_millisecond()}></span></p>
             </div>
         </footer>
     }
@@ -168,6 +288,8 @@ elements.forEach(element => {
                 <meta name="viewport" content="width=device-width" />
                 <link rel="stylesheet" href={format!("{}{}", config.base_url, config.styles.website)} />
                 <link href=format!("{}atom.xml", config.base_url) type="application/atom+xml" rel="alternate" title="Sitewide Atom feed" />
+                <link href=format!("{}rss.xml", config.base_url) type="application/rss+xml" rel="alternate" title="Sitewide RSS feed" />
+                <link href=format!("{}feed.json", config.base_url) type="application/feed+json" rel="alternate" title="Sitewide JSON feed" />
                 <title>{title}</title>
                 {additional_meta.into_any()}
             </head>
@@ -176,7 +298,7 @@ elements.forEach(element => {
                 <main class=tw_join!("flex-grow")>
                     {children.into_any()}
                 </main>
-                {footer(&config.timestamp)}
+                {footer(&config)}
                 <script inner_html=relative_timestamp></script>
                 {additional_js.into_any()}
             </body>
@@ -214,6 +336,7 @@ fn nav_and_content(header: impl IntoAny, children: impl IntoAny) -> AnyView {
     .into_any()
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn blog(
     error: bool,
     page_title: &str,
@@ -240,6 +363,7 @@ pub fn blog(
     .into_any()
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn home(
     page_title: &str,
     website_title: &str,
@@ -0,0 +1,98 @@
+use std::path::Path;
+
+/// Blurred placeholders of raster images found in content assets, shown while they are loading
+#[derive(Debug, Clone, Copy)]
+pub struct Blurhash {
+    /// Include a script decoding placeholders in pages, images fade in over them once loaded.
+    /// Without it, the average color of opaque images is the only placeholder.
+    pub decoder_script: bool,
+}
+
+/// Images are downscaled before computing their blurhash, details are lost anyway
+const THUMBNAIL_SIZE: u32 = 32;
+const COMPONENTS_X: u32 = 4;
+const COMPONENTS_Y: u32 = 3;
+
+/// Placeholder of an image
+#[derive(Debug, Clone)]
+pub(crate) struct Placeholder {
+    blurhash: String,
+    /// Average color, only for images without transparency
+    color: Option<[u8; 3]>,
+}
+
+impl Placeholder {
+    /// Attributes to put on the `<img>` element
+    pub fn attributes(&self) -> String {
+        let (blurhash, style) = self.values();
+        match style {
+            Some(style) => format!(" data-blurhash=\"{blurhash}\" style=\"{style}\""),
+            None => format!(" data-blurhash=\"{blurhash}\""),
+        }
+    }
+
+    /// Values of the `data-blurhash` and `style` attributes
+    pub fn values(&self) -> (String, Option<String>) {
+        (
+            self.blurhash.clone(),
+            self.color
+                .map(|[r, g, b]| format!("background-color: #{r:02x}{g:02x}{b:02x}")),
+        )
+    }
+}
+
+/// Compute the placeholder of a raster image
+pub(crate) fn placeholder(path: &Path) -> Option<Placeholder> {
+    if !crate::responsive_images::is_raster(path) {
+        return None;
+    }
+
+    let image = match image::ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(image::ImageError::IoError)
+        .and_then(|reader| reader.decode())
+    {
+        Ok(image) => image,
+        Err(e) => {
+            println!("Blurhash: could not decode `{}`: {e}", path.display());
+            return None;
+        }
+    };
+
+    let thumbnail = image
+        .resize_exact(
+            THUMBNAIL_SIZE,
+            THUMBNAIL_SIZE,
+            image::imageops::FilterType::Triangle,
+        )
+        .into_rgba8();
+
+    let blurhash = match blurhash::encode(
+        COMPONENTS_X,
+        COMPONENTS_Y,
+        thumbnail.width(),
+        thumbnail.height(),
+        thumbnail.as_raw(),
+    ) {
+        Ok(blurhash) => blurhash,
+        Err(e) => {
+            println!("Blurhash: could not encode `{}`: {e}", path.display());
+            return None;
+        }
+    };
+
+    // A background color would show through transparent parts of the image
+    let opaque = thumbnail.pixels().all(|pixel| pixel[3] == u8::MAX);
+    let color = opaque.then(|| {
+        let pixels = thumbnail.pixels().len() as u64;
+        let sum = thumbnail.pixels().fold([0u64; 3], |mut sum, pixel| {
+            sum.iter_mut()
+                .zip(pixel.0)
+                .for_each(|(sum, channel)| *sum += channel as u64);
+            sum
+        });
+        sum.map(|channel| (channel / pixels) as u8)
+    });
+
+    Some(Placeholder { blurhash, color })
+}
@@ -1,14 +1,22 @@
+mod components;
 mod config;
 mod content;
 mod feed;
 mod html;
+mod image_placeholder;
 mod pages;
+mod responsive_images;
 
 const LANG: &str = "en";
 const RFC_3339_FORMAT: &str = "%FT%T%:z";
 
-pub use config::{BuildConfig, Styles};
-pub use content::{Content, GenerateHtmlError};
+pub use components::{Attributes, Component, ComponentError};
+pub use config::{BuildConfig, ExternalLinks, RawHtml, Styles};
+pub use content::{
+    Content, GenerateHtmlError, IncludeError, NonBreakingSpaces, RustCheck, Typography,
+};
+pub use image_placeholder::Blurhash;
+pub use responsive_images::{ImageFormat, ResponsiveImages};
 
 use atom_syndication::Feed;
 use leptos::prelude::{AnyView, RenderHtml};
@@ -43,6 +51,12 @@ pub enum BlogWriteFilesError {
 
     #[error("Failed to write Opengraph image to {0}: {1}")]
     WriteOpengraphImage(PathBuf, std::io::ErrorKind),
+
+    #[error("Failed to generate variants of image {0}: {1}")]
+    ResizeImage(PathBuf, image::ImageError),
+
+    #[error("Failed to serialize JSON feed: {0}")]
+    SerializeJsonFeed(serde_json::Error),
 }
 
 #[derive(Debug)]
@@ -51,6 +65,20 @@ struct CopyAsset {
     target: PathBuf,
 }
 
+/// File generated during the build, such as a diagram
+#[derive(Debug)]
+struct WriteAsset {
+    target: PathBuf,
+    contents: String,
+}
+
+#[derive(Debug)]
+struct ResizeImage {
+    source: PathBuf,
+    target_directory: PathBuf,
+    variants: Vec<responsive_images::Variant>,
+}
+
 #[cfg(feature = "opengraph")]
 struct OpengraphPage {
     slug: crate::content::Slug,
@@ -75,7 +103,11 @@ pub struct Blog<'config> {
     config: BuildConfig<'config>,
     pages: Vec<Page>,
     assets: Vec<CopyAsset>,
+    generated_assets: Vec<WriteAsset>,
+    resize_images: Vec<ResizeImage>,
     atom_feed: Option<Feed>,
+    rss_feed: Option<rss::Channel>,
+    json_feed: Option<feed::JsonFeed>,
 }
 
 #[cfg(debug_assertions)]
@@ -99,7 +131,11 @@ impl<'config> Blog<'config> {
             config,
             pages: vec![],
             assets: vec![],
+            generated_assets: vec![],
+            resize_images: vec![],
             atom_feed: None,
+            rss_feed: None,
+            json_feed: None,
         }
     }
 
@@ -151,13 +187,34 @@ impl<'config> Blog<'config> {
             // Stop when any failure occurs
             .collect::<Result<Vec<_>, _>>()?
             .into_iter()
+            .map(|(content, view)| {
+                if self.config.rust_check.is_some() {
+                    content.check_rust_code_blocks()?;
+                }
+
+                // Diagrams are saved next to the content, for feeds
+                for (file_name, svg) in content.diagrams()? {
+                    self.generated_assets.push(WriteAsset {
+                        target: self
+                            .paths
+                            .target
+                            .join(WWW_FOLDER)
+                            .join(content.slug())
+                            .join(file_name),
+                        contents: svg,
+                    });
+                }
+                Ok((content, view))
+            })
+            .collect::<Result<Vec<_>, GenerateHtmlError>>()?
+            .into_iter()
             .map(|(content, view)| ProcessedContent {
                 slug: content.slug(),
                 view,
 
                 #[cfg(feature = "opengraph")]
                 opengraph: opengraph::template::content(
-                    content.meta().title(),
+                    &content.title(&self.config),
                     self.config.logo,
                     self.config.website_name,
                     self.config.absolute_url().as_ref(),
@@ -181,7 +238,12 @@ impl<'config> Blog<'config> {
         Ok(())
     }
 
-    fn add_assets(assets: &mut Vec<CopyAsset>, source_base: &Path, target_base: &Path) {
+    fn add_assets(
+        assets: &mut Vec<CopyAsset>,
+        source_base: &Path,
+        target_base: &Path,
+        excluded_file_names: &[&str],
+    ) {
         // Gather list of source assets
         let source_assets = walkdir::WalkDir::new(source_base)
             .into_iter()
@@ -189,7 +251,12 @@ impl<'config> Blog<'config> {
             .map(|dir_entry| dir_entry.into_path())
             .filter(|path| path.is_file())
             // Remove markdown file as assets
-            .filter(|path| path.extension().map(|ext| ext != "md").unwrap_or(true));
+            .filter(|path| path.extension().map(|ext| ext != "md").unwrap_or(true))
+            .filter(|path| {
+                path.file_name().is_none_or(|name| {
+                    !excluded_file_names.iter().any(|excluded| name == *excluded)
+                })
+            });
 
         // For each source asset, get its target path
         let source_and_target_assets = source_assets.filter_map(|source| {
@@ -221,12 +288,45 @@ impl<'config> Blog<'config> {
                 let source_base = content_path.as_ref().join(assets);
                 let target_base = self.paths.target.join(WWW_FOLDER).join(slug);
 
-                Self::add_assets(&mut self.assets, &source_base, &target_base);
+                // Metadata of components stays private
+                Self::add_assets(
+                    &mut self.assets,
+                    &source_base,
+                    &target_base,
+                    components::METADATA_FILES,
+                );
             });
+
+        // Resized variants of raster images in content assets
+        if let Some(responsive_images) = self.config.responsive_images {
+            for asset in &self.assets {
+                let variants = responsive_images::variants(&asset.source, &responsive_images);
+                if let (false, Some(target_directory)) =
+                    (variants.is_empty(), asset.target.parent())
+                {
+                    self.resize_images.push(ResizeImage {
+                        source: asset.source.clone(),
+                        target_directory: target_directory.to_path_buf(),
+                        variants,
+                    });
+                }
+            }
+        }
     }
 
-    pub fn add_atom_feed(&mut self, content: &[Content]) {
-        self.atom_feed = Some(feed::create_feed(&self.config, content));
+    pub fn add_atom_feed(&mut self, content: &[Content]) -> Result<(), GenerateHtmlError> {
+        self.atom_feed = Some(feed::create_feed(&self.config, content)?);
+        Ok(())
+    }
+
+    pub fn add_rss_feed(&mut self, content: &[Content]) -> Result<(), GenerateHtmlError> {
+        self.rss_feed = Some(feed::create_rss_feed(&self.config, content)?);
+        Ok(())
+    }
+
+    pub fn add_json_feed(&mut self, content: &[Content]) -> Result<(), GenerateHtmlError> {
+        self.json_feed = Some(feed::create_json_feed(&self.config, content)?);
+        Ok(())
     }
 
     fn write_view_to_file(
@@ -298,6 +398,30 @@ impl<'config> Blog<'config> {
         Ok(path)
     }
 
+    fn write_rss_feed(
+        rss_feed: rss::Channel,
+        target: &Path,
+    ) -> Result<PathBuf, BlogWriteFilesError> {
+        let path = target.join(format!("{WWW_FOLDER}rss.xml"));
+        std::fs::write(&path, rss_feed.to_string())
+            .map_err(|e| BlogWriteFilesError::WriteFile(path.clone(), e.kind()))?;
+
+        Ok(path)
+    }
+
+    fn write_json_feed(
+        json_feed: feed::JsonFeed,
+        target: &Path,
+    ) -> Result<PathBuf, BlogWriteFilesError> {
+        let path = target.join(format!("{WWW_FOLDER}feed.json"));
+        let json =
+            serde_json::to_string(&json_feed).map_err(BlogWriteFilesError::SerializeJsonFeed)?;
+        std::fs::write(&path, json)
+            .map_err(|e| BlogWriteFilesError::WriteFile(path.clone(), e.kind()))?;
+
+        Ok(path)
+    }
+
     /// Consume struct, write to files
     pub fn build(mut self) -> Result<PathBuf, BlogWriteFilesError> {
         for Page {
@@ -388,6 +512,7 @@ impl<'config> Blog<'config> {
             &mut self.assets,
             PathBuf::from(self.config.assets).as_path(),
             self.paths.target.join(WWW_FOLDER).as_path(),
+            &[],
         );
 
         // Copy content assets + internal assets
@@ -395,11 +520,48 @@ impl<'config> Blog<'config> {
             Self::copy_asset(&copy_asset.source, &copy_asset.target)?;
         }
 
+        // Write files generated from content
+        for generated_asset in self.generated_assets {
+            let parent =
+                generated_asset
+                    .target
+                    .parent()
+                    .ok_or(BlogWriteFilesError::NoParentFolder(
+                        generated_asset.target.clone(),
+                    ))?;
+            std::fs::create_dir_all(parent)
+                .map_err(|e| BlogWriteFilesError::CreateFolder(e.kind()))?;
+            std::fs::write(&generated_asset.target, generated_asset.contents).map_err(|e| {
+                BlogWriteFilesError::WriteFile(generated_asset.target.clone(), e.kind())
+            })?;
+            println!("Wrote `{}`", generated_asset.target.display());
+        }
+
+        // Resize images from content assets
+        for resize_image in self.resize_images {
+            responsive_images::generate(
+                &resize_image.source,
+                &resize_image.target_directory,
+                &resize_image.variants,
+            )
+            .map_err(|e| BlogWriteFilesError::ResizeImage(resize_image.source.clone(), e))?;
+        }
+
         // Atom feed
         if let Some(atom_feed) = self.atom_feed {
             Self::write_atom_feed(atom_feed, &self.paths.target)?;
         }
 
+        // RSS feed
+        if let Some(rss_feed) = self.rss_feed {
+            Self::write_rss_feed(rss_feed, &self.paths.target)?;
+        }
+
+        // JSON feed
+        if let Some(json_feed) = self.json_feed {
+            Self::write_json_feed(json_feed, &self.paths.target)?;
+        }
+
         Ok(self.paths.target)
     }
 }
@@ -41,9 +41,14 @@ pub fn content(
             "Posted on {} ",
             content.meta().datetime().strftime("%B %d, %Y at %R"),
         )}<span data-relative-timestamp={content.meta().datetime().timestamp().as_millisecond()}></span></div>
+            <div class=tw_join!("text-gray-600", "dark:text-gray-400") data-testid="reading-time">{format!(
+            "{} min read, {} words",
+            content.reading_time(),
+            content.word_count(),
+        )}</div>
     };
 
-    let content_html = content.generate_html()?;
+    let content_html = content.generate_html(&config)?;
 
     // Calling `content.next()` because the list is sorted in descending order
     let previous_navigation = content.next().map(|slug| {
@@ -60,20 +65,42 @@ pub fn content(
     }).unwrap_or(().into_any());
 
     // Additional JS
-    let additional_js = view! {
-        {crate::html::syntax_highlight(content.code_block_languages()).into_any()}
-        {additional_js.map(|js| js.into_any()).unwrap_or(().into_any())}
+    let additional_js = additional_js
+        .map(|js| js.into_any())
+        .unwrap_or(().into_any());
+
+    // Stylesheet for code blocks, only when there are some: it also styles blocks without language
+    let syntax_highlight = match content.has_code_blocks() {
+        true => crate::html::syntax_highlight().into_any(),
+        false => ().into_any(),
+    };
+
+    // Copy button on code blocks, only when there are some
+    let code_copy_button = match content.has_code_blocks() {
+        true => crate::html::code_copy_button().into_any(),
+        false => ().into_any(),
     };
 
+    // Blurhash decoder, only when placeholders are requested
+    let blurhash_decoder = config
+        .blurhash
+        .filter(|blurhash| blurhash.decoder_script)
+        .map(|_| crate::html::blurhash_decoder().into_any())
+        .unwrap_or(().into_any());
+
     let url = format!("{}{}", config.absolute_url(), content.slug());
+    let description = content.description(&config);
     let additional_meta = view! {
+        {syntax_highlight}
+        {code_copy_button}
+        {blurhash_decoder}
         <link rel="canonical" href=url.clone() />
         <meta name="author" content=config.content_author />
         {
             #[cfg(feature = "opengraph")]
-            add_opengraph_property("og:description", content.meta().description()).into_any()
+            description.clone().map(|description| add_opengraph_property("og:description", description).into_any())
         }
-        <meta name="description" content=content.meta().description() />
+        {description.map(|description| view! { <meta name="description" content=description /> })}
         {
             #[cfg(feature = "opengraph")]
             add_opengraph_property("og:type", "article").into_any()
@@ -90,7 +117,7 @@ pub fn content(
 
     Ok(crate::html::blog(
         false,
-        content.meta().title(),
+        &content.title(&config),
         config.website_name,
         subtitle,
         config,
@@ -100,7 +127,7 @@ pub fn content(
             {next_navigation}
         }),
         leptos::html::article().inner_html(content_html),
-        Some(additional_js),
+        additional_js,
         Some(additional_meta),
     ))
 }
@@ -115,9 +142,11 @@ pub fn index<'a>(
         .map(|content| {
             leptos::view! {
                 <li class=tw_join!("flex", "flex-col", "lg:flex-wrap", "items-start")>
-                    <a class=tw_join!("font-medium", "text-lg") href={format!("{}{}", config.base_url, content.slug())} >{content.meta().title()}</a>
+                    <a class=tw_join!("font-medium", "text-lg") href={format!("{}{}", config.base_url, content.slug())} >{content.title(&config)}</a>
                     " "
                     <time datetime=content.meta().datetime().strftime(crate::RFC_3339_FORMAT).to_string() class=tw_join!("flex-none", "text-gray-600", "dark:text-gray-400", "text-lg")>{content.meta().datetime().strftime("%F").to_string()}</time>
+                    <span class=tw_join!("text-gray-600", "dark:text-gray-400") data-testid="reading-time">{format!("{} min read", content.reading_time())}</span>
+                    {content.excerpt(&config).map(|excerpt| view! { <p class=tw_join!("mt-2") data-testid="excerpt">{excerpt}</p> })}
                 </li>
             }
         }).collect_view();
@@ -126,7 +155,7 @@ pub fn index<'a>(
         .external_url
         .map(|url| {
             view! {
-                <li>{underline_link(url, view!{ {icon_website(None)}"Website" }, None)}</li>
+                <li>{underline_external_link(&config, url, view!{ {icon_website(None)}"Website" }, None)}</li>
             }
             .into_any()
         })
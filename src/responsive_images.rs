@@ -0,0 +1,298 @@
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+use std::path::Path;
+
+/// Resized variants of raster images found in content assets
+#[derive(Debug, Clone, Copy)]
+pub struct ResponsiveImages<'a> {
+    /// Widths of the variants, in pixels. Widths larger than the original image are skipped.
+    pub widths: &'a [u32],
+    /// Additional formats offered to browsers supporting them, as `<source>` in a `<picture>`
+    pub formats: &'a [ImageFormat],
+    /// Value of the `sizes` attribute, describing the width of images in the layout
+    pub sizes: &'a str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    Avif,
+    Webp,
+    Jpeg,
+}
+
+impl ImageFormat {
+    fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "avif" => Some(ImageFormat::Avif),
+            "webp" => Some(ImageFormat::Webp),
+            "jpg" | "jpeg" => Some(ImageFormat::Jpeg),
+            _ => None,
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Avif => "avif",
+            ImageFormat::Webp => "webp",
+            ImageFormat::Jpeg => "jpg",
+        }
+    }
+
+    fn mime_type(&self) -> &'static str {
+        match self {
+            ImageFormat::Avif => "image/avif",
+            ImageFormat::Webp => "image/webp",
+            ImageFormat::Jpeg => "image/jpeg",
+        }
+    }
+}
+
+/// Raster images that can be resized, other images are left untouched
+const RASTER_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp"];
+
+/// A resized copy of an image, next to the original one
+#[derive(Debug, Clone)]
+pub(crate) struct Variant {
+    pub width: u32,
+    /// `None` when the variant keeps the format of the original image
+    pub format: Option<ImageFormat>,
+    pub file_name: String,
+}
+
+pub(crate) fn is_raster(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| RASTER_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// List the variants of an image, in the original format and in additional formats
+pub(crate) fn variants(path: &Path, config: &ResponsiveImages) -> Vec<Variant> {
+    if !is_raster(path) {
+        return vec![];
+    }
+    let (Some(stem), Some(extension)) = (
+        path.file_stem().and_then(|stem| stem.to_str()),
+        path.extension().and_then(|ext| ext.to_str()),
+    ) else {
+        return vec![];
+    };
+    let original_width = match imagesize::size(path) {
+        Ok(size) => size.width as u32,
+        Err(e) => {
+            println!(
+                "Responsive images: could not get size of `{}`: {e}",
+                path.display()
+            );
+            return vec![];
+        }
+    };
+
+    let mut widths = config
+        .widths
+        .iter()
+        .copied()
+        .filter(|width| *width < original_width)
+        .collect::<Vec<_>>();
+    widths.sort();
+    widths.dedup();
+
+    // Additional formats also get a variant with the width of the original image. The format of
+    // the original image is not additional, its variants would have the same file names.
+    let original_format = ImageFormat::from_extension(extension);
+    let formats = std::iter::once(None).chain(
+        config
+            .formats
+            .iter()
+            .copied()
+            .filter(|format| Some(*format) != original_format)
+            .map(Some),
+    );
+    formats
+        .flat_map(|format| {
+            let mut widths = widths.clone();
+            if format.is_some() {
+                widths.push(original_width);
+            }
+            widths.into_iter().map(move |width| Variant {
+                width,
+                format,
+                file_name: format!(
+                    "{stem}-{width}w.{}",
+                    format.map(|format| format.extension()).unwrap_or(extension)
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Characters separating candidates of a `srcset`, encoded in their URLs
+const SRCSET_SEPARATORS: &AsciiSet = &CONTROLS.add(b' ').add(b',');
+
+/// Characters of file names breaking a `srcset`, its attribute, or the URL of a candidate
+const FILE_NAME: &AsciiSet = &SRCSET_SEPARATORS
+    .add(b'"')
+    .add(b'\'')
+    .add(b'<')
+    .add(b'>')
+    .add(b'&')
+    .add(b'#')
+    .add(b'?')
+    .add(b'%');
+
+/// `<picture>` element with every variant of an image, falling back on the `<img>` element
+pub(crate) fn picture(
+    src: &str,
+    img_attributes: &str,
+    variants: &[Variant],
+    original_width: Option<usize>,
+    sizes: &str,
+    config: &ResponsiveImages,
+) -> String {
+    // Variants are located next to the original image
+    let directory = match src.rfind('/') {
+        Some(idx) => &src[..=idx],
+        None => "",
+    };
+    let directory = utf8_percent_encode(directory, SRCSET_SEPARATORS).to_string();
+    let srcset = |format: Option<ImageFormat>| {
+        variants
+            .iter()
+            .filter(|variant| variant.format == format)
+            .map(|variant| {
+                format!(
+                    "{directory}{} {}w",
+                    utf8_percent_encode(&variant.file_name, FILE_NAME),
+                    variant.width
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let sources = config
+        .formats
+        .iter()
+        .map(|format| {
+            let srcset = srcset(Some(*format));
+            if srcset.is_empty() {
+                return String::new();
+            }
+            format!(
+                "<source type=\"{}\" srcset=\"{}\" sizes=\"{sizes}\" />",
+                format.mime_type(),
+                srcset.join(", "),
+            )
+        })
+        .collect::<String>();
+
+    // The original image is the largest one in its format
+    let mut img_srcset = srcset(None);
+    if let Some(original_width) = original_width {
+        img_srcset.push(format!(
+            "{} {original_width}w",
+            utf8_percent_encode(src, SRCSET_SEPARATORS)
+        ));
+    }
+
+    format!(
+        "<picture>{sources}<img src=\"{src}\" srcset=\"{}\" sizes=\"{sizes}\"{img_attributes} /></picture>",
+        img_srcset.join(", "),
+    )
+}
+
+/// Write resized variants of an image in a directory
+pub(crate) fn generate(
+    source: &Path,
+    target_directory: &Path,
+    variants: &[Variant],
+) -> Result<(), image::ImageError> {
+    if variants.is_empty() {
+        return Ok(());
+    }
+
+    let original = image::ImageReader::open(source)?
+        .with_guessed_format()?
+        .decode()?;
+
+    for variant in variants {
+        let target = target_directory.join(&variant.file_name);
+        let height = (original.height() as f64 * variant.width as f64 / original.width() as f64)
+            .round()
+            .max(1.0) as u32;
+        let resized =
+            original.resize_exact(variant.width, height, image::imageops::FilterType::Lanczos3);
+
+        match variant.format {
+            // JPEG does not support transparency
+            Some(ImageFormat::Jpeg) => resized
+                .into_rgb8()
+                .save_with_format(&target, image::ImageFormat::Jpeg)?,
+            Some(ImageFormat::Webp) => {
+                resized.save_with_format(&target, image::ImageFormat::WebP)?
+            }
+            Some(ImageFormat::Avif) => {
+                resized.save_with_format(&target, image::ImageFormat::Avif)?
+            }
+            None => resized.save(&target)?,
+        }
+        println!("Responsive images: wrote `{}`", target.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variants_in_original_format() {
+        let path =
+            std::env::temp_dir().join(format!("leptos_ssg-variants-{}.webp", std::process::id()));
+        image::RgbImage::new(640, 480).save(&path).unwrap();
+        let responsive_images = ResponsiveImages {
+            widths: &[320],
+            formats: &[ImageFormat::Avif, ImageFormat::Webp],
+            sizes: "100vw",
+        };
+        let variants = variants(&path, &responsive_images);
+        std::fs::remove_file(&path).unwrap();
+
+        let mut file_names = variants
+            .iter()
+            .map(|variant| variant.file_name.as_str())
+            .collect::<Vec<_>>();
+        file_names.sort();
+        let stem = path.file_stem().unwrap().to_str().unwrap();
+        assert_eq!(
+            file_names,
+            [
+                format!("{stem}-320w.avif"),
+                format!("{stem}-320w.webp"),
+                format!("{stem}-640w.avif"),
+            ]
+        );
+    }
+
+    #[test]
+    fn file_names() {
+        let variants = [Variant {
+            width: 320,
+            format: Some(ImageFormat::Webp),
+            file_name: "a \"b\", c%-320w.webp".into(),
+        }];
+        let responsive_images = ResponsiveImages {
+            widths: &[320],
+            formats: &[ImageFormat::Webp],
+            sizes: "100vw",
+        };
+        let picture = picture(
+            "images/a &quot;b&quot;, c%.png",
+            "",
+            &variants,
+            Some(640),
+            "100vw",
+            &responsive_images,
+        );
+        assert!(picture.contains("srcset=\"images/a%20%22b%22%2C%20c%25-320w.webp 320w\""));
+        assert!(picture.contains("srcset=\"images/a%20&quot;b&quot;%2C%20c%.png 640w\""));
+    }
+}